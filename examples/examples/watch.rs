@@ -1,5 +1,6 @@
 use std::{
   process::{Command, Output},
+  sync::mpsc,
   thread,
   time::Duration,
 };
@@ -11,7 +12,7 @@ use intuitive::{
   components::{stack::Flex::*, HStack, Section, Text, VStack},
   error::Result,
   on_key, render,
-  state::State,
+  state::use_receiver,
   style::Color,
   terminal::Terminal,
   text::Span,
@@ -37,8 +38,8 @@ fn render(interval: u64, command: String) {
 }
 
 #[component(CommandOutput)]
-fn render(output: State<Option<Output>>) {
-  let text = output.inspect(|output| match output {
+fn render(output: Option<Output>) {
+  let text = match output {
     Some(output) => {
       if output.status.success() {
         Span::new(String::from_utf8_lossy(&output.stdout), Color::White)
@@ -48,15 +49,39 @@ fn render(output: State<Option<Output>>) {
     }
 
     None => Span::new(".. waiting for command to finish ..", Color::Gray),
-  });
+  };
 
   render! {
     Text(text)
   }
 }
 
+/// Runs `args.command` every `args.interval` seconds, sending its output through the returned receiver.
+fn run(args: Args) -> mpsc::Receiver<Output> {
+  let (sender, receiver) = mpsc::channel();
+
+  thread::spawn(move || {
+    let mut cmd = Command::new("sh");
+    let cmd = cmd.args(["-c".to_string(), args.command]);
+
+    let interval = Duration::from_secs(args.interval);
+
+    loop {
+      if sender.send(cmd.output().expect("cmd::output panicked")).is_err() {
+        break;
+      }
+
+      thread::sleep(interval);
+    }
+  });
+
+  receiver
+}
+
 #[component(Root)]
-fn render(args: Args, output: State<Option<Output>>) {
+fn render(args: Args) {
+  let output = use_receiver(|| run(args.clone()), |output, new| *output = Some(new));
+
   let on_key = on_key! {
     KeyEvent { code: Char('q'), .. } => event::quit()
   };
@@ -64,7 +89,7 @@ fn render(args: Args, output: State<Option<Output>>) {
   render! {
     VStack(flex: [Block(3), Grow(1)], on_key) {
       Top(interval: args.interval, command: args.command.clone())
-      CommandOutput(output: output.clone())
+      CommandOutput(output)
     }
   }
 }
@@ -80,26 +105,5 @@ struct Args {
 }
 
 fn main() -> Result<()> {
-  let args = Args::parse();
-  let output = State::<Option<Output>>::default();
-
-  {
-    let output = output.clone();
-    let args = args.clone();
-
-    thread::spawn(move || {
-      let mut cmd = Command::new("sh");
-      let cmd = cmd.args(["-c".to_string(), args.command]);
-
-      let interval = Duration::from_secs(args.interval);
-
-      loop {
-        output.set(Some(cmd.output().expect("cmd::output panicked")));
-
-        thread::sleep(interval);
-      }
-    });
-  }
-
-  Terminal::new(Root::new(args, output))?.run()
+  Terminal::new(Root::new(Args::parse()))?.run()
 }
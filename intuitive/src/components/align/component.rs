@@ -0,0 +1,196 @@
+use super::{Alignment, Length};
+use crate::{
+  component,
+  components::children::Children,
+  element::{Any as AnyElement, Element},
+  event::{handler::Propagate, KeyEvent, KeyHandler, MouseEvent, MouseHandler},
+  terminal::{Frame, Rect, Size},
+};
+
+/// A component for positioning its contents within the available space.
+///
+/// The contents are `width` cells wide and `height` cells high, and are positioned by
+/// `horizontal` and `vertical`, which default to [`Alignment::Center`]. A [`Length`] is
+/// either a fixed number of cells, a percentage of the available space, or the size
+/// measured by the contents, and the contents fill the available space along an axis
+/// without one. For example,
+/// ```rust
+/// # use intuitive::{
+/// #   component,
+/// #   components::{align::{Alignment, Length}, Align, Section, Text},
+/// #   render,
+/// # };
+/// #
+/// #[component(Popup)]
+/// fn render() {
+///   render! {
+///     Align(width: Length::Percentage(50), height: 5) {
+///       Section(title: "Popup")
+///     }
+///   }
+/// }
+///
+/// #[component(StatusBar)]
+/// fn render() {
+///   render! {
+///     Align(vertical: Alignment::End, height: 1) {
+///       Text(text: "ready")
+///     }
+///   }
+/// }
+/// ```
+///
+/// Events that are not handled by the contents bubble up to the `on_key` and `on_mouse`
+/// handlers, while `on_key_capture` and `on_mouse_capture` receive events before the
+/// contents do, as described in [`Element`].
+///
+/// [`Alignment::Center`]: align/enum.Alignment.html#variant.Center
+/// [`Element`]: ../element/trait.Element.html
+/// [`Length`]: align/enum.Length.html
+#[component(Align)]
+pub fn render(
+  horizontal: Alignment,
+  vertical: Alignment,
+  width: Option<Length>,
+  height: Option<Length>,
+  children: Children<1>,
+  on_key: KeyHandler,
+  on_key_capture: KeyHandler,
+  on_mouse: MouseHandler,
+  on_mouse_capture: MouseHandler,
+) {
+  AnyElement::new(Frozen {
+    horizontal: *horizontal,
+    vertical: *vertical,
+    width: *width,
+    height: *height,
+
+    content: children[0].render(),
+    on_key: on_key.clone(),
+    on_key_capture: on_key_capture.clone(),
+    on_mouse: on_mouse.clone(),
+    on_mouse_capture: on_mouse_capture.clone(),
+  })
+}
+
+struct Frozen {
+  horizontal: Alignment,
+  vertical: Alignment,
+  width: Option<Length>,
+  height: Option<Length>,
+
+  content: AnyElement,
+  on_key: KeyHandler,
+  on_key_capture: KeyHandler,
+  on_mouse: MouseHandler,
+  on_mouse_capture: MouseHandler,
+}
+
+impl Frozen {
+  /// Returns the size of the contents in `available` cells.
+  fn size(&self, available: Size) -> Size {
+    let measured = if [self.width, self.height].contains(&Some(Length::Auto)) {
+      self.content.measure(available)
+    } else {
+      available
+    };
+
+    Size {
+      width: self
+        .width
+        .map_or(available.width, |width| width.resolve(available.width, measured.width)),
+      height: self
+        .height
+        .map_or(available.height, |height| height.resolve(available.height, measured.height)),
+    }
+  }
+
+  fn inner(&self, rect: Rect) -> Rect {
+    let Size { width, height } = self.size(rect.into());
+
+    Rect {
+      x: rect.x + self.horizontal.offset(width, rect.width),
+      y: rect.y + self.vertical.offset(height, rect.height),
+      width,
+      height,
+    }
+  }
+}
+
+impl Element for Frozen {
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
+  }
+
+  fn on_key_capture(&self, event: KeyEvent) -> Propagate {
+    self.on_key_capture.handle(event)
+  }
+
+  fn on_mouse(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+    self.on_mouse.handle(event)
+  }
+
+  fn on_mouse_capture(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+    self.on_mouse_capture.handle(event)
+  }
+
+  fn measure(&self, available: Size) -> Size {
+    self.size(available)
+  }
+
+  fn draw(&self, rect: Rect, frame: &mut Frame) {
+    self.content.draw(self.inner(rect), frame);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use crate::{
+    components::{
+      align::{Alignment::*, Length::*},
+      Align, Any as AnyComponent, Text,
+    },
+    element::Any as AnyElement,
+    event::tree::{self, rect_at},
+    render,
+    terminal::Rect,
+  };
+
+  #[test]
+  #[serial]
+  fn aligns_contents() {
+    let align: AnyComponent = render! {
+      Align(width: Fixed(4), height: Percentage(50)) {
+        Text(text: "a")
+      }
+    };
+
+    let element: AnyElement = align.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 10));
+    assert_eq!(rect_at(3, 2), Some(Rect::new(3, 2, 4, 5)));
+    assert_eq!(rect_at(0, 0), Some(Rect::new(0, 0, 10, 10)));
+
+    let align: AnyComponent = render! {
+      Align(horizontal: End, vertical: Start, width: 20, height: Fixed(1)) {
+        Text(text: "a")
+      }
+    };
+
+    let element: AnyElement = align.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 10));
+    assert_eq!(rect_at(0, 0), Some(Rect::new(0, 0, 10, 1)));
+    assert_eq!(rect_at(0, 1), Some(Rect::new(0, 0, 10, 10)));
+
+    let align: AnyComponent = render! {
+      Align(vertical: End, height: Fixed(2)) {
+        Text(text: "a")
+      }
+    };
+
+    let element: AnyElement = align.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 10));
+    assert_eq!(rect_at(9, 8), Some(Rect::new(0, 8, 10, 2)));
+  }
+}
@@ -0,0 +1,73 @@
+//! Structures relating to the `Align` component.
+
+pub(super) mod component;
+
+use crate::components::param::Literal;
+
+/// The position of a child of an [`Align`] along one axis.
+///
+/// [`Align`]: ../struct.Align.html
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+  /// Align the child to the left or to the top.
+  Start,
+  /// Center the child.
+  #[default]
+  Center,
+  /// Align the child to the right or to the bottom.
+  End,
+}
+
+impl Alignment {
+  /// Returns the offset of a child of size `size` aligned in `available` cells.
+  pub(crate) fn offset(self, size: u16, available: u16) -> u16 {
+    let free = available.saturating_sub(size);
+
+    match self {
+      Self::Start => 0,
+      Self::Center => free / 2,
+      Self::End => free,
+    }
+  }
+}
+
+/// The width or height of a child of an [`Align`].
+///
+/// A `Length` can be created from a `u16`, which is converted into a [`Length::Fixed`], so
+/// a fixed length can be passed as a number, as in `width: 20`.
+///
+/// [`Align`]: ../struct.Align.html
+/// [`Length::Fixed`]: enum.Length.html#variant.Fixed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Length {
+  /// A fixed number of cells, shrinking to the available cells.
+  Fixed(u16),
+  /// A percentage of the available cells, at most 100.
+  Percentage(u16),
+  /// The number of cells measured by the child, through [`Element::measure`].
+  ///
+  /// [`Element::measure`]: ../../element/trait.Element.html#method.measure
+  Auto,
+}
+
+impl Length {
+  /// Returns the number of cells of the length in `available` cells, for a child
+  /// measuring `measured` cells.
+  pub(crate) fn resolve(self, available: u16, measured: u16) -> u16 {
+    match self {
+      Self::Fixed(size) => size.min(available),
+      Self::Percentage(percent) => (u32::from(available) * u32::from(percent.min(100)) / 100) as u16,
+      Self::Auto => measured.min(available),
+    }
+  }
+}
+
+impl From<u16> for Length {
+  fn from(size: u16) -> Self {
+    Self::Fixed(size)
+  }
+}
+
+impl Literal for Length {
+  type Value = u16;
+}
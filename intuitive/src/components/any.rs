@@ -39,6 +39,13 @@ impl Deref for Any {
   }
 }
 
+/// `Any`s are equal if they were cloned from one another.
+impl PartialEq for Any {
+  fn eq(&self, other: &Self) -> bool {
+    Arc::ptr_eq(&self.0, &other.0)
+  }
+}
+
 impl<C: Component + 'static + Send + Sync> From<C> for Any {
   fn from(component: C) -> Self {
     Self::new(component)
@@ -1,13 +1,15 @@
 use crate::{
   component,
-  components::{children::Children, Embed, Empty, HStack, VStack},
-  event::KeyHandler,
-  on_key, render,
+  components::children::Children,
+  element::{Any as AnyElement, Element},
+  event::{handler::Propagate, KeyEvent, KeyHandler},
+  terminal::{Frame, Rect},
 };
 
 /// A component for centering its contents.
 ///
-/// For example,
+/// The contents take up the middle third of the available width and height. To size
+/// or position them otherwise, use [`Align`]. For example,
 /// ```rust
 /// # use intuitive::{component, components::{Centered, Section}, render};
 /// #
@@ -20,33 +22,47 @@ use crate::{
 ///   }
 /// }
 /// ```
+///
+/// [`Align`]: struct.Align.html
 #[component(Centered)]
 pub fn render(children: Children<1>, on_key: KeyHandler) {
-  let child = children[0].render();
+  AnyElement::new(Frozen {
+    content: children[0].render(),
+    on_key: on_key.clone(),
+  })
+}
 
-  let on_key = on_key! { [child, on_key]
-    event => on_key.handle_or(event, |event| child.on_key(event))
-  };
+struct Frozen {
+  content: AnyElement,
+  on_key: KeyHandler,
+}
 
-  render! {
-    VStack(on_key) {
-      Empty()
-      HStack() {
-        Empty()
-        Embed(content: child)
-        Empty()
-      }
-      Empty()
-    }
+impl Element for Frozen {
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
+  }
+
+  fn draw(&self, rect: Rect, frame: &mut Frame) {
+    let centered = Rect {
+      x: rect.x + rect.width / 3,
+      y: rect.y + rect.height / 3,
+      width: rect.width / 3,
+      height: rect.height / 3,
+    };
+
+    self.content.draw(centered, frame);
   }
 }
 
 #[cfg(test)]
 mod tests {
+  use serial_test::serial;
+
   use super::*;
-  use crate::{components::Text, element::Any as AnyElement, state::State};
+  use crate::{components::Text, event::tree, on_key, render, state::State};
 
   #[test]
+  #[serial]
   fn centered_forwards_keys() {
     pub use crate::event::{KeyCode::*, KeyEvent, KeyModifiers};
 
@@ -63,7 +79,8 @@ mod tests {
       }
     };
 
-    centered.on_key(KeyEvent::new(Esc, KeyModifiers::NONE));
+    tree::draw(&centered, Rect::new(0, 0, 30, 30));
+    tree::dispatch_key(KeyEvent::new(Esc, KeyModifiers::NONE));
 
     assert_eq!(called.get(), true);
   }
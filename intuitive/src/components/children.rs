@@ -1,4 +1,13 @@
 //! Structures for dealing with child components.
+//!
+//! Components that take a fixed number of children accept a [`Children<N>`], while
+//! components that take any number of children, such as [`HStack`] and [`VStack`], accept a
+//! [`List`], whose length is only known at runtime.
+//!
+//! [`Children<N>`]: struct.Children.html
+//! [`HStack`]: ../struct.HStack.html
+//! [`List`]: struct.List.html
+//! [`VStack`]: ../struct.VStack.html
 
 use std::ops::Deref;
 
@@ -12,7 +21,7 @@ use crate::element::Any as AnyElement;
 ///
 /// [`render`]: #method.render
 /// [`Default`]: https://doc.rust-lang.org/std/default/trait.Default.html
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Children<const N: usize>([AnyComponent; N]);
 
 impl<const N: usize> Children<N> {
@@ -47,3 +56,56 @@ impl<const N: usize> From<[AnyComponent; N]> for Children<N> {
     Self(children)
   }
 }
+
+/// A list of child components, whose length is only known at runtime.
+///
+/// Like [`Children<N>`], the main purpose of `List` is to provide a [`render`] method,
+/// and to implement [`Default`]. A `List` can be created from an array or a `Vec` of
+/// components, or collected from an iterator of components. Collections of components
+/// can also be spread into the children of a component in a [`render!`] macro.
+///
+/// [`Children<N>`]: struct.Children.html
+/// [`Default`]: https://doc.rust-lang.org/std/default/trait.Default.html
+/// [`render`]: #method.render
+/// [`render!`]: ../../macro.render.html
+#[derive(Clone, Default, PartialEq)]
+pub struct List(Vec<AnyComponent>);
+
+impl List {
+  /// Render the components in the list.
+  pub fn render(&self) -> Vec<AnyElement> {
+    self.0.iter().map(|component| component.render()).collect()
+  }
+}
+
+impl Deref for List {
+  type Target = [AnyComponent];
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl<const N: usize> From<[AnyComponent; N]> for List {
+  fn from(children: [AnyComponent; N]) -> Self {
+    Self(children.into())
+  }
+}
+
+impl From<Vec<AnyComponent>> for List {
+  fn from(children: Vec<AnyComponent>) -> Self {
+    Self(children)
+  }
+}
+
+impl<const N: usize> From<Children<N>> for List {
+  fn from(children: Children<N>) -> Self {
+    Self(children.0.into())
+  }
+}
+
+impl FromIterator<AnyComponent> for List {
+  fn from_iter<I: IntoIterator<Item = AnyComponent>>(children: I) -> Self {
+    Self(children.into_iter().collect())
+  }
+}
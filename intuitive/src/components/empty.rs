@@ -1,6 +1,7 @@
 use crate::{
   component,
   element::{Any as AnyElement, Element},
+  terminal::Size,
 };
 
 /// A component that renders nothing.
@@ -9,4 +10,8 @@ pub fn render() -> AnyElement {
   AnyElement::new(Self {})
 }
 
-impl Element for Empty {}
+impl Element for Empty {
+  fn measure(&self, _available: Size) -> Size {
+    Size::default()
+  }
+}
@@ -0,0 +1,254 @@
+//! A module containing the `Help` and `HintLine` components.
+
+use crate::{
+  component,
+  components::{
+    children::Children,
+    experimental_components::{
+      modal::{use_modal, Modal},
+      overlay::{Frozen, Search},
+    },
+    Embed, Text,
+  },
+  element::Any as AnyElement,
+  event::{handler::Hint, KeyHandler},
+  focus, on_key, render,
+  state::{use_state, State},
+  style::{Modifier, Style},
+  text::{Lines, Span, Spans},
+};
+
+/// A component showing a searchable overlay describing the active key bindings.
+///
+/// Pressing `?`, when the focused component does not handle it, shows an overlay, using a
+/// [`Modal`], that lists the [`Hint`]s of the
+/// `on_key` handlers of the focused component chain, as returned by [`focus::hints`],
+/// followed by the [`Hint`]s of the `on_key` handler of the `Help` itself. While the
+/// overlay is shown, typed characters filter the listed bindings, and `Esc` hides it.
+///
+/// [`Hint`]s are attached to handlers through [`Handler::hint`], or by describing the
+/// bindings of a [`Keymap`]:
+/// ```rust
+/// # use intuitive::{component, components::{experimental::help::Help, Section}, event::{self, keymap::Keymap}, render, state::use_state};
+/// #
+/// #[component(Root)]
+/// fn render() {
+///   let keymap = use_state(|| {
+///     Keymap::new()
+///       .bind("ctrl+c", event::quit)
+///       .describe("Quit the application")
+///   });
+///
+///   render! {
+///     Help(on_key: keymap.get()) {
+///       Section(title: "Press ? for help")
+///     }
+///   }
+/// }
+/// ```
+///
+/// [`focus::hints`]: ../../../focus/fn.hints.html
+/// [`Handler::hint`]: ../../../event/handler/struct.Handler.html#method.hint
+/// [`Hint`]: ../../../event/handler/struct.Hint.html
+/// [`Keymap`]: ../../../event/keymap/struct.Keymap.html
+/// [`Modal`]: ../modal/struct.Modal.html
+#[component(Help)]
+pub fn render(children: Children<1>, on_key: KeyHandler) {
+  render! {
+    Modal() {
+      Toggle(on_key) {
+        Embed(content: children[0].clone())
+      }
+    }
+  }
+}
+
+/// Shows the help overlay in the nearest [`Modal`] when `?` is pressed.
+///
+/// The key is handled once it bubbles up, so that focused components, such as text
+/// inputs, receive it first.
+///
+/// [`Modal`]: ../modal/struct.Modal.html
+#[component(Toggle)]
+fn render(children: Children<1>, on_key: KeyHandler) {
+  let modal = use_modal();
+  let query = use_state(String::new);
+
+  let content = children[0].render();
+
+  let on_key = on_key! { [modal, query, on_key]
+    KeyEvent { code: Char('?'), .. } if !modal.is_shown() => {
+      let mut hints = focus::hints();
+      hints.extend(on_key.hints().iter().cloned());
+
+      query.set(String::new());
+      modal.show(render! {
+        Overlay(hints, query: query.clone())
+      });
+    }
+
+    event => on_key.handle(event),
+  };
+
+  AnyElement::new(Frozen {
+    content,
+    on_key: on_key.into(),
+    on_key_capture: KeyHandler::default(),
+  })
+}
+
+/// The overlay listing the [`Hint`]s that match the query.
+///
+/// [`Hint`]: ../../../event/handler/struct.Hint.html
+#[component(Overlay)]
+fn render(hints: Vec<Hint>, query: State<String>) {
+  let lines = query.inspect(|query| lines(hints, query));
+
+  render! {
+    Search(title: "Help", prompt: "/", query: query.clone(), lines)
+  }
+}
+
+/// Returns whether `hint` matches `query`, ignoring case.
+fn matches(hint: &Hint, query: &str) -> bool {
+  let query = query.to_lowercase();
+
+  hint.keys.to_lowercase().contains(&query) || hint.description.to_lowercase().contains(&query)
+}
+
+/// Lists the `hints` matching `query`, one per line, with their keys aligned.
+fn lines(hints: &[Hint], query: &str) -> Lines {
+  let hints: Vec<&Hint> = hints.iter().filter(|hint| matches(hint, query)).collect();
+  let width = hints.iter().map(|hint| hint.keys.chars().count()).max().unwrap_or(0);
+
+  let bold = Style::new(None, None, Modifier::BOLD);
+
+  Lines(
+    hints
+      .into_iter()
+      .map(|hint| {
+        Spans::new(vec![
+          Span::new(format!("{:width$}", hint.keys, width = width), bold),
+          Span::from("  "),
+          Span::from(&hint.description),
+        ])
+      })
+      .collect(),
+  )
+}
+
+/// A component showing the [`Hint`]s of the active key bindings on a single line.
+///
+/// The [`Hint`]s of the focused component chain, as returned by [`focus::hints`], are
+/// shown followed by `hints`. This is typically rendered at the bottom of the screen:
+/// ```rust
+/// # use intuitive::{component, components::{experimental::help::HintLine, stack::Flex::*, Section, VStack}, event::handler::Hint, render};
+/// #
+/// #[component(Root)]
+/// fn render() {
+///   render! {
+///     VStack(flex: [Grow(1), Block(1)]) {
+///       Section(title: "Content")
+///       HintLine(hints: vec![Hint::new("?", "help")])
+///     }
+///   }
+/// }
+/// ```
+///
+/// [`focus::hints`]: ../../../focus/fn.hints.html
+/// [`Hint`]: ../../../event/handler/struct.Hint.html
+#[component(HintLine)]
+pub fn render(hints: Vec<Hint>) {
+  let mut all = focus::hints();
+  all.extend(hints.iter().cloned());
+
+  let bold = Style::new(None, None, Modifier::BOLD);
+  let mut spans = Vec::new();
+
+  for (i, hint) in all.into_iter().enumerate() {
+    if i > 0 {
+      spans.push(Span::from("  "));
+    }
+
+    spans.push(Span::new(hint.keys, bold));
+    spans.push(Span::from(format!(" {}", hint.description)));
+  }
+
+  render! {
+    Text(text: Spans::new(spans))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use super::*;
+  use crate::{
+    components::{Any as AnyComponent, Focusable},
+    event::{handler::Propagate, tree, KeyCode, KeyEvent, KeyModifiers},
+    state,
+    terminal::Rect,
+  };
+
+  #[test]
+  #[serial]
+  fn focused_components_handle_question_mark_first() {
+    let question_mark = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE);
+
+    for propagate in [Propagate::Stop, Propagate::Next] {
+      let received = State::new(0);
+      let on_key = on_key! { [received]
+        _ => {
+          received.update(|received| received + 1);
+          return propagate;
+        }
+      };
+
+      let help: AnyComponent = render! {
+        Help() {
+          Focusable(on_key) {
+            Text()
+          }
+        }
+      };
+
+      state::reset();
+      focus::render_done();
+
+      let element = help.render();
+      state::render_done().unwrap();
+      focus::render_done();
+      focus::next();
+      tree::draw(&element, Rect::new(0, 0, 10, 10));
+
+      // the overlay is only shown when the focused component does not handle the key
+      assert_eq!(tree::dispatch_key(question_mark), Propagate::Stop);
+      assert_eq!(received.get(), 1);
+    }
+  }
+
+  #[test]
+  fn lines_filter_and_align() {
+    let hints = [
+      Hint::new("ctrl+s", "Save the file"),
+      Hint::new("q", "Quit"),
+      Hint::new("g g", "Go to the top"),
+    ];
+
+    let text = |lines: Lines| -> Vec<String> {
+      lines
+        .0
+        .iter()
+        .map(|spans| spans.iter().map(|span| span.text.as_str()).collect())
+        .collect()
+    };
+
+    assert_eq!(
+      text(lines(&hints, "")),
+      vec!["ctrl+s  Save the file", "q       Quit", "g g     Go to the top"]
+    );
+    assert_eq!(text(lines(&hints, "QU")), vec!["q  Quit"]);
+    assert_eq!(text(lines(&hints, "ctrl")), vec!["ctrl+s  Save the file"]);
+  }
+}
@@ -6,13 +6,16 @@ use tui::{text::Spans as TuiSpans, widgets::Paragraph};
 
 use crate::{
   component,
-  components::{stack::Flex::*, Empty, Section, VStack},
+  components::{
+    align::{Alignment, Length},
+    Align, Section,
+  },
   element::{Any as AnyElement, Element},
   event::{KeyHandler, MouseHandler},
   on_key, render,
   state::use_state,
   style::Style,
-  terminal::{Frame, Rect},
+  terminal::{Frame, Rect, Size},
   text::Spans,
 };
 
@@ -23,50 +26,42 @@ use crate::{
 ///   - scrolling on overflow
 ///   - supports navigating with arrow keys
 ///   - supports navigating with `ctrl+a` and `ctrl+e`
-///   - has a fixed single-line height of 3 rows
+///   - has a fixed single-line height of 3 rows, which stacks size it to with
+///     [`Flex::Auto`]
 ///
 /// ## Vertical Alignment
 ///
-/// Since this component always renders as three blocks high,
-/// when more space is available it vertically centers itself.
-/// In order to align this element to the top, you will need
-/// to use [`VStack`] along with [`Flex::Block`] and properly
-/// route the key events:
+/// Since this component always renders as three blocks high, when more space is
+/// available it aligns itself vertically according to `align`, centering itself by
+/// default. In order to align this element to the top, pass [`Alignment::Start`], and
+/// wrap the input in a [`Focusable`] so that key events are routed to it:
 ///
 /// ```rust
 /// # use intuitive::{
 /// #   component,
-/// #   components::{stack::Flex::*, Embed, Empty, experimental::input::Input, VStack},
-/// #   element,
-/// #   error::Result,
+/// #   components::{align::Alignment, experimental::input::Input, Focusable},
 /// #   on_key, render,
-/// #   terminal::Terminal,
 /// # };
 /// #
 /// #[component(Root)]
 /// fn render() {
-///   let input: element::Any = render! {
-///     Input(title: "Input Box")
-///   };
-///
-///   let on_key = on_key! { [input]
+///   let on_key = on_key! {
 ///     KeyEvent { code: Esc, .. } => event::quit(),
-///     event => input.on_key(event),
 ///   };
 ///
 ///   render! {
-///     VStack(flex: [Block(3), Grow(1)], on_key) {
-///       Embed(content: input)
-///       Empty()
+///     Focusable(on_key) {
+///       Input(title: "Input Box", align: Alignment::Start)
 ///     }
 ///   }
 /// }
 /// ```
 ///
-/// [`VStack`]: ../../struct.VStack.html
-/// [`Flex::Block`]: ../../stack/enum.Flex.html#variant.Block
+/// [`Alignment::Start`]: ../../align/enum.Alignment.html#variant.Start
+/// [`Flex::Auto`]: ../../stack/enum.Flex.html#variant.Auto
+/// [`Focusable`]: ../../struct.Focusable.html
 #[component(Input)]
-pub fn render(title: Spans, border: Style, on_key: KeyHandler, on_mouse: MouseHandler) {
+pub fn render(title: Spans, border: Style, align: Alignment, on_key: KeyHandler, on_mouse: MouseHandler) {
   let cursor = use_state(|| 0usize);
   let text = use_state(|| String::new());
 
@@ -95,15 +90,11 @@ pub fn render(title: Spans, border: Style, on_key: KeyHandler, on_mouse: MouseHa
     },
   });
 
-  // TODO(enricozb): consider adding (Vertical)Alignment as a property, that will
-  // align the input box to the top/middle/bottom.
   render! {
-    VStack(flex: [Grow(1), Block(3), Grow(1)], on_key) {
-      Empty()
+    Align(vertical: *align, height: Length::Auto, on_key) {
       Section(title, border, on_mouse) {
         Inner(cursor: cursor.get(), text: text.get())
       }
-      Empty()
     }
   }
 }
@@ -122,6 +113,10 @@ struct Frozen {
 }
 
 impl Element for Frozen {
+  fn measure(&self, available: Size) -> Size {
+    Size::new(available.width, 1).min(available)
+  }
+
   fn draw(&self, rect: Rect, frame: &mut Frame) {
     let (text, cursor) = if self.cursor < rect.width {
       (self.text.clone().into(), rect.x + self.cursor)
@@ -4,7 +4,10 @@
 //! This is a staging ground for potential future components. Furthermore, components
 //! here may or may not have any accompanying documentation.
 
+pub mod help;
 pub mod input;
 pub mod modal;
+mod overlay;
+pub mod palette;
 pub mod scroll;
 pub mod table;
@@ -12,9 +12,13 @@ pub use self::hook::{use_modal, Funcs};
 use crate::{
   components::{children::Children, Component},
   element::{Any as AnyElement, Element},
-  event::{KeyEvent, KeyHandler},
-  state::use_state,
-  terminal::{Frame, Rect},
+  event::{
+    handler::Propagate,
+    tree::{self, Route},
+    KeyEvent, KeyHandler,
+  },
+  state::{component_scope, use_state},
+  terminal::{Frame, Rect, Size},
 };
 
 /// A component supporting modal-style overlays.
@@ -64,12 +68,17 @@ use crate::{
 /// as a child of a [`Modal`]. Then, in any descendant of this [`Modal`], we can call
 /// [`use_modal`] to mutate the state of that [`Modal`].
 ///
+/// While a component is shown, key events target it instead of the children of the
+/// [`Modal`], even if one of them is focused. Events it does not handle, as indicated by
+/// a returned [`Propagate::Next`], bubble up to the `on_key` handler of the [`Modal`].
+///
 /// # Internals
 /// The [`Modal`] is somewhat special in that it does not (yet) use the built-in
 /// [`use_state`] hooks, but instead has its own internal `static` hook manager.
 ///
 /// [`Modal`]: struct.Modal.html
 /// [`Component`]: trait.Component.html
+/// [`Propagate::Next`]: ../../../event/handler/enum.Propagate.html#variant.Next
 /// [`use_state`]: ../../state/fn.use_state.html
 /// [`use_modal`]: fn.use_modal.html
 #[derive(Default)]
@@ -80,13 +89,15 @@ pub struct Modal {
 
 impl Component for Modal {
   fn render(&self) -> AnyElement {
+    let _scope = component_scope("Modal");
+
     let modal = use_state(|| None);
     let funcs = use_state(|| Funcs::new(modal.clone()));
 
     hook::set_modal_funcs(funcs.get());
 
     AnyElement::new(Frozen {
-      modal: modal.get().map(|modal| modal.render()),
+      modal: modal.get().map(|modal| AnyElement::new(Shown(modal.render()))),
 
       content: self.children[0].render(),
       on_key: self.on_key.clone(),
@@ -102,8 +113,12 @@ struct Frozen {
 }
 
 impl Element for Frozen {
-  fn on_key(&self, event: KeyEvent) {
-    self.on_key.handle_or(event, |event| self.content.on_key(event));
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
+  }
+
+  fn measure(&self, available: Size) -> Size {
+    self.content.measure(available)
   }
 
   fn draw(&self, rect: Rect, frame: &mut Frame) {
@@ -114,3 +129,20 @@ impl Element for Frozen {
     }
   }
 }
+
+/// The element shown by a [`Modal`], which key events are routed to.
+///
+/// [`Modal`]: struct.Modal.html
+struct Shown(AnyElement);
+
+impl Element for Shown {
+  fn measure(&self, available: Size) -> Size {
+    self.0.measure(available)
+  }
+
+  fn draw(&self, rect: Rect, frame: &mut Frame) {
+    tree::route(Route::Modal);
+
+    self.0.draw(rect, frame);
+  }
+}
@@ -0,0 +1,74 @@
+//! The parts shared by the components that show a searchable overlay in a [`Modal`].
+//!
+//! [`Modal`]: ../modal/struct.Modal.html
+
+use crate::{
+  component,
+  components::{experimental_components::modal::use_modal, stack::Flex::*, Centered, Section, Text, VStack},
+  element::{Any as AnyElement, Element},
+  event::{handler::Propagate, KeyEvent, KeyHandler},
+  on_key, render,
+  state::State,
+  style::Color,
+  terminal::{Frame, Rect, Size},
+  text::Lines,
+};
+
+/// An overlay listing `lines` below the `query` being typed, preceded by `prompt`.
+///
+/// Keys are first passed to `on_key`. Those it does not handle edit the query, and `Esc`
+/// hides the overlay.
+///
+/// This component does not use any hooks, as it is only rendered while it is shown.
+#[component(Search)]
+pub(super) fn render(title: String, prompt: String, query: State<String>, lines: Lines, on_key: KeyHandler) {
+  let modal = use_modal();
+
+  let edit = on_key! { [modal, query]
+    KeyEvent { code: Esc, .. } => modal.hide(),
+
+    KeyEvent { code: Char(c), .. } => query.mutate(|query| query.push(c)),
+    KeyEvent { code: Backspace, .. } => query.mutate(|query| {
+      query.pop();
+    }),
+  };
+
+  let on_key = on_key.then(edit);
+
+  render! {
+    Centered(on_key) {
+      Section(title, border: Color::Blue) {
+        VStack(flex: [Block(1), Grow(1)]) {
+          Text(text: format!("{}{}", prompt, query.get()))
+          Text(text: lines.clone())
+        }
+      }
+    }
+  }
+}
+
+/// The element of a component that shows an overlay when a key is pressed, drawing its
+/// `content` with the given handlers.
+pub(super) struct Frozen {
+  pub content: AnyElement,
+  pub on_key: KeyHandler,
+  pub on_key_capture: KeyHandler,
+}
+
+impl Element for Frozen {
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
+  }
+
+  fn on_key_capture(&self, event: KeyEvent) -> Propagate {
+    self.on_key_capture.handle(event)
+  }
+
+  fn measure(&self, available: Size) -> Size {
+    self.content.measure(available)
+  }
+
+  fn draw(&self, rect: Rect, frame: &mut Frame) {
+    self.content.draw(rect, frame);
+  }
+}
@@ -0,0 +1,403 @@
+//! A module containing the `CommandPalette` component and related hooks.
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::{
+  component,
+  components::{
+    children::Children,
+    experimental_components::{
+      modal::{use_modal, Modal},
+      overlay::{Frozen, Search},
+    },
+    Embed,
+  },
+  element::Any as AnyElement,
+  event::{keymap::Key, KeyHandler},
+  on_key, render,
+  state::{self, use_state, State},
+  style::{Color, Modifier, Style},
+  text::{Lines, Span, Spans},
+};
+
+/// The commands registered through [`use_command`] by the descendants of each
+/// [`CommandPalette`] that is currently rendering, innermost last.
+///
+/// [`CommandPalette`]: struct.CommandPalette.html
+/// [`use_command`]: fn.use_command.html
+static REGISTERING: Mutex<Vec<Vec<Command>>> = Mutex::new(Vec::new());
+
+/// A named command that can be run from a [`CommandPalette`].
+///
+/// [`CommandPalette`]: struct.CommandPalette.html
+#[derive(Clone)]
+pub struct Command {
+  name: String,
+  description: String,
+  keys: Option<String>,
+  action: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl Command {
+  /// Creates a command named `name` that calls `action` when selected.
+  pub fn new<F>(name: &str, action: F) -> Self
+  where
+    F: Fn() + 'static + Send + Sync,
+  {
+    Self {
+      name: name.to_string(),
+      description: String::new(),
+      keys: None,
+      action: Arc::new(action),
+    }
+  }
+
+  /// Sets the description shown next to the name of the command.
+  pub fn description(mut self, description: &str) -> Self {
+    self.description = description.to_string();
+    self
+  }
+
+  /// Sets the keys that also run the command, which are shown next to its description.
+  ///
+  /// This is only displayed, and does not bind the keys.
+  pub fn keys(mut self, keys: &str) -> Self {
+    self.keys = Some(keys.to_string());
+    self
+  }
+
+  /// Runs the command.
+  pub fn run(&self) {
+    (self.action)();
+  }
+}
+
+/// The key that opens a [`CommandPalette`], `ctrl+p` by default.
+///
+/// [`CommandPalette`]: struct.CommandPalette.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shortcut(pub Key);
+
+impl Default for Shortcut {
+  fn default() -> Self {
+    Self::from("ctrl+p")
+  }
+}
+
+impl From<Key> for Shortcut {
+  fn from(key: Key) -> Self {
+    Self(key)
+  }
+}
+
+impl From<&Shortcut> for Shortcut {
+  fn from(shortcut: &Shortcut) -> Self {
+    *shortcut
+  }
+}
+
+impl From<&str> for Shortcut {
+  /// # Panics
+  /// Panics if `key` is not a valid [`Key`].
+  ///
+  /// [`Key`]: ../../../event/keymap/struct.Key.html
+  fn from(key: &str) -> Self {
+    Self(key.parse().unwrap_or_else(|err| panic!("{}", err)))
+  }
+}
+
+/// Registers `command` with the nearest ancestor [`CommandPalette`].
+///
+/// Like [`use_modal`], calls to `use_command` may only be within a call to
+/// [`Component::render`] of a component that is a descendant of a [`CommandPalette`].
+/// Commands are registered anew on every render, so a command can be registered
+/// conditionally.
+///
+/// # Panics
+/// Panics if called outside of a [`CommandPalette`].
+///
+/// [`CommandPalette`]: struct.CommandPalette.html
+/// [`Component::render`]: ../../trait.Component.html#tymethod.render
+/// [`use_modal`]: ../modal/fn.use_modal.html
+pub fn use_command(command: Command) {
+  state::effect(move || {
+    REGISTERING
+      .lock()
+      .last_mut()
+      .expect("use_command called outside of a CommandPalette or outside of render")
+      .push(command.clone());
+  });
+}
+
+/// A component providing a fuzzy-searchable list of commands.
+///
+/// Pressing the `shortcut`, `ctrl+p` by default, shows an overlay using a [`Modal`],
+/// listing the `commands` passed to the `CommandPalette`, followed by those registered
+/// by its descendants through [`use_command`]. While the overlay is shown, typed
+/// characters filter the commands, `Up` and `Down` change the selected command, `Enter`
+/// runs it, and `Esc` hides the overlay.
+///
+/// For example,
+/// ```rust
+/// # use intuitive::{
+/// #   component,
+/// #   components::{experimental::palette::{use_command, Command, CommandPalette}, Section},
+/// #   event, render,
+/// # };
+/// #
+/// #[component(Editor)]
+/// fn render() {
+///   use_command(Command::new("Quit", event::quit).description("Quit the editor").keys("ctrl+c"));
+///
+///   render! {
+///     Section(title: "Press ctrl+k to open the command palette")
+///   }
+/// }
+///
+/// #[component(Root)]
+/// fn render() {
+///   render! {
+///     CommandPalette(shortcut: "ctrl+k") {
+///       Editor()
+///     }
+///   }
+/// }
+/// ```
+///
+/// [`Modal`]: ../modal/struct.Modal.html
+/// [`use_command`]: fn.use_command.html
+#[component(CommandPalette)]
+pub fn render(children: Children<1>, commands: Vec<Command>, shortcut: Shortcut, on_key: KeyHandler) {
+  render! {
+    Modal() {
+      Toggle(commands: Vec::clone(commands), shortcut, on_key) {
+        Embed(content: children[0].clone())
+      }
+    }
+  }
+}
+
+/// Shows the palette in the nearest [`Modal`] when the shortcut is pressed.
+///
+/// [`Modal`]: ../modal/struct.Modal.html
+#[component(Toggle)]
+fn render(children: Children<1>, commands: Vec<Command>, shortcut: Shortcut, on_key: KeyHandler) {
+  let modal = use_modal();
+  let query = use_state(String::new);
+  let selected = use_state(|| 0usize);
+
+  // the registration is an effect, so that it surrounds the effects of the children when
+  // a memoized ancestor replays them
+  let registered = commands.clone();
+  state::effect(move || REGISTERING.lock().push(registered.clone()));
+  let content = children[0].render();
+  let commands = REGISTERING.lock().last().cloned().expect("registered commands");
+  state::effect(|| drop(REGISTERING.lock().pop()));
+
+  let shortcut = shortcut.0;
+
+  let on_key_capture = on_key! { [modal, query, selected]
+    event if Key::from(event) == shortcut && !modal.is_shown() => {
+      query.set(String::new());
+      selected.set(0);
+
+      modal.show(render! {
+        Overlay(commands: commands.clone(), query: query.clone(), selected: selected.clone())
+      });
+    }
+
+    _ => return Propagate::Next,
+  };
+
+  AnyElement::new(Frozen {
+    content,
+    on_key: on_key.clone(),
+    on_key_capture: on_key_capture.into(),
+  })
+}
+
+/// The overlay listing the commands that match the query.
+#[component(Overlay)]
+fn render(commands: Vec<Command>, query: State<String>, selected: State<usize>) {
+  let modal = use_modal();
+  let matching = query.inspect(|query| search(commands, query));
+
+  let on_key = on_key! { [modal, selected, matching]
+    KeyEvent { code: Enter, .. } => {
+      modal.hide();
+
+      if let Some(command) = matching.get(selected.get()) {
+        command.run();
+      }
+    }
+
+    KeyEvent { code: Up, .. } => selected.update(|selected| selected.saturating_sub(1)),
+    KeyEvent { code: Down, .. } => selected.update(|selected| (selected + 1).min(matching.len().saturating_sub(1))),
+
+    // the query is edited by the overlay, after selecting the first matching command
+    KeyEvent { code: Char(_) | Backspace, .. } => {
+      selected.set(0);
+      return Propagate::Next;
+    }
+
+    _ => return Propagate::Next,
+  };
+
+  let lines = lines(&matching, selected.get());
+
+  render! {
+    Search(title: "Commands", prompt: "> ", query: query.clone(), lines, on_key)
+  }
+}
+
+/// Scores how well `query` fuzzily matches `text`, ignoring case.
+///
+/// Every character of `query` must appear in `text`, in order. Matches are scored higher
+/// when the characters are consecutive, or start a word, and lower the later they start.
+fn score(query: &str, text: &str) -> Option<i64> {
+  let text: Vec<char> = text.to_lowercase().chars().collect();
+
+  let mut score = 0;
+  let mut next = 0;
+  let mut previous: Option<usize> = None;
+
+  for c in query.to_lowercase().chars() {
+    let idx = next + text[next..].iter().position(|&other| other == c)?;
+
+    score += match previous {
+      Some(previous) if previous + 1 == idx => 8,
+      _ if idx == 0 || !text[idx - 1].is_alphanumeric() => 6,
+      _ => 1,
+    };
+
+    if previous.is_none() {
+      score -= idx as i64;
+    }
+
+    previous = Some(idx);
+    next = idx + 1;
+  }
+
+  Some(score)
+}
+
+/// Returns the `commands` matching `query`, best matches first.
+///
+/// Commands are matched by name, and otherwise by description, ranking below all commands
+/// matched by name.
+fn search(commands: &[Command], query: &str) -> Vec<Command> {
+  let mut scored: Vec<(i64, &Command)> = commands
+    .iter()
+    .filter_map(|command| {
+      let by_name = score(query, &command.name);
+      let by_description = || score(query, &command.description).map(|score| score - 1000);
+
+      by_name.or_else(by_description).map(|score| (score, command))
+    })
+    .collect();
+
+  // the sort is stable, so equally good matches keep their order
+  scored.sort_by_key(|(score, _)| -score);
+  scored.into_iter().map(|(_, command)| command.clone()).collect()
+}
+
+fn lines(commands: &[Command], selected: usize) -> Lines {
+  let bold = Style::new(None, None, Modifier::BOLD);
+  let dim = Style::new(Some(Color::Gray), None, Modifier::empty());
+  let highlight = Style::new(Some(Color::Black), Some(Color::Blue), Modifier::BOLD);
+
+  Lines(
+    commands
+      .iter()
+      .enumerate()
+      .map(|(i, command)| {
+        let mut spans = vec![Span::new(&command.name, if i == selected { highlight } else { bold })];
+
+        if !command.description.is_empty() {
+          spans.push(Span::new(format!("  {}", command.description), dim));
+        }
+
+        if let Some(keys) = &command.keys {
+          spans.push(Span::new(format!("  ({})", keys), dim));
+        }
+
+        Spans::new(spans)
+      })
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use super::*;
+
+  #[test]
+  fn fuzzy_search() {
+    let commands: Vec<Command> = ["Save File", "Save All", "Open Recent", "Toggle Sidebar"]
+      .iter()
+      .map(|name| Command::new(name, || ()))
+      .chain([Command::new("Quit", || ()).description("Close the application")])
+      .collect();
+
+    let names = |query: &str| -> Vec<String> { search(&commands, query).into_iter().map(|command| command.name).collect() };
+
+    assert_eq!(names("s"), vec!["Save File", "Save All", "Toggle Sidebar", "Quit"]);
+    assert_eq!(names("all"), vec!["Save All"]);
+    assert_eq!(names("ts"), vec!["Toggle Sidebar"]);
+    assert_eq!(names("close"), vec!["Quit"]);
+    assert!(names("xyz").is_empty());
+  }
+
+  #[test]
+  #[serial]
+  fn use_command_registers_with_palette() {
+    REGISTERING.lock().push(Vec::new());
+    use_command(Command::new("Quit", || ()));
+    let commands = REGISTERING.lock().pop().unwrap();
+
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].name, "Quit");
+  }
+
+  #[test]
+  #[serial]
+  fn use_command_within_memoized_component() {
+    use crate::{
+      components::{Any as AnyComponent, Empty},
+      focus,
+    };
+
+    #[component(Child)]
+    fn render() {
+      use_command(Command::new("Quit", || ()));
+
+      render! { Empty() }
+    }
+
+    #[component(App, memo)]
+    fn render() {
+      render! {
+        CommandPalette() {
+          Child()
+        }
+      }
+    }
+
+    state::reset();
+    focus::render_done();
+
+    // the second render reuses the element of `App`, replaying the effects of its render
+    for _ in 0..2 {
+      let app: AnyComponent = render! { App() };
+      app.render();
+      state::render_done().unwrap();
+      focus::render_done();
+
+      assert!(REGISTERING.lock().is_empty());
+    }
+  }
+}
@@ -11,8 +11,8 @@ use tui::{
 use crate::{
   components::Component,
   element::{Any as AnyElement, Element},
-  event::{KeyEvent, KeyHandler, MouseEvent, MouseEventKind},
-  state::{use_state, State},
+  event::{handler::Propagate, KeyEvent, KeyHandler, MouseEvent, MouseEventKind},
+  state::{component_scope, use_state, State},
   style::Style,
   terminal::{Frame, Rect},
   text::{Lines, Spans},
@@ -34,6 +34,8 @@ pub struct Scroll {
 
 impl Component for Scroll {
   fn render(&self) -> AnyElement {
+    let _scope = component_scope("Scroll");
+
     let buffer_offset = use_state(|| 0);
 
     AnyElement::new(Frozen {
@@ -74,19 +76,21 @@ impl Frozen {
 }
 
 impl Element for Frozen {
-  fn on_key(&self, event: KeyEvent) {
-    self.on_key.handle(event);
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
   }
 
-  fn on_mouse(&self, rect: Rect, event: MouseEvent) {
+  fn on_mouse(&self, rect: Rect, event: MouseEvent) -> Propagate {
     match event.kind {
       MouseEventKind::ScrollDown => self
         .buffer_offset
         .update(|offset| cmp::min(self.max_buffer_offset(rect), offset + 1)),
       MouseEventKind::ScrollUp => self.buffer_offset.update(|offset| offset.saturating_sub(1)),
 
-      _ => (),
+      _ => return Propagate::Next,
     }
+
+    Propagate::Stop
   }
 
   fn draw(&self, rect: Rect, frame: &mut Frame) {
@@ -10,10 +10,10 @@ use self::widget::Table as TableWidget;
 use crate::{
   components::Component,
   element::{Any as AnyElement, Element},
-  event::{KeyEvent, KeyHandler},
+  event::{handler::Propagate, KeyEvent, KeyHandler},
   on_key,
-  state::{use_state, State},
-  terminal::{Frame, Rect},
+  state::{component_scope, use_state, State},
+  terminal::{Frame, Rect, Size},
   text::Spans,
 };
 
@@ -28,6 +28,8 @@ pub struct Table<const N: usize> {
 
 impl<const N: usize> Component for Table<N> {
   fn render(&self) -> AnyElement {
+    let _scope = component_scope("Table");
+
     let index = use_state(|| 0);
 
     AnyElement::new(Frozen {
@@ -48,21 +50,30 @@ struct Frozen<const N: usize> {
   on_key: KeyHandler,
 }
 
+impl<const N: usize> Frozen<N> {
+  fn widget(&self) -> TableWidget<'_, N> {
+    let rows = self.rows.iter().cloned().map(|row| row.map(TuiSpans::from)).collect();
+
+    TableWidget::new(rows, self.alignments.into())
+  }
+}
+
 impl<const N: usize> Element for Frozen<N> {
-  fn on_key(&self, event: KeyEvent) {
+  fn on_key(&self, event: KeyEvent) -> Propagate {
     self.on_key.handle_or(
       event,
       on_key! {
         KeyEvent { code: Char('j'), .. } => self.index.update(|i| if i + 1 < self.rows.len() { i + 1 } else { *i }),
         KeyEvent { code: Char('k'), .. } => self.index.update(|i| i.saturating_sub(1)),
       },
-    );
+    )
   }
 
-  fn draw(&self, rect: Rect, frame: &mut Frame) {
-    let rows = self.rows.iter().cloned().map(|row| row.map(TuiSpans::from)).collect();
-    let widget = TableWidget::new(rows, self.alignments.into());
+  fn measure(&self, available: Size) -> Size {
+    self.widget().size().min(available)
+  }
 
-    frame.render_widget(widget, rect);
+  fn draw(&self, rect: Rect, frame: &mut Frame) {
+    frame.render_widget(self.widget(), rect);
   }
 }
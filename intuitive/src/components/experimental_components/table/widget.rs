@@ -7,6 +7,10 @@ use tui::{
 };
 
 use super::alignment::Alignment;
+use crate::terminal::Size;
+
+/// The number of cells between columns, which is the default of the tui table.
+const COLUMN_SPACING: u16 = 1;
 
 type Rows<'a, const N: usize> = Vec<[Spans<'a>; N]>;
 
@@ -36,14 +40,28 @@ impl<'a, const N: usize> Table<'a, N> {
   }
 
   #[allow(clippy::cast_possible_truncation)]
-  fn constraints(&self) -> [Constraint; N] {
+  fn widths(&self) -> [u16; N] {
     let lengths: Vec<[usize; N]> = self.lengths();
-    let mut constraints = [0; N];
+    let mut widths = [0; N];
     for i in 0..N {
-      constraints[i] = lengths.iter().map(|l| l[i]).max().unwrap_or_default() as u16;
+      widths[i] = lengths.iter().map(|l| l[i]).max().unwrap_or_default() as u16;
     }
 
-    constraints.map(Constraint::Length)
+    widths
+  }
+
+  fn constraints(&self) -> [Constraint; N] {
+    self.widths().map(Constraint::Length)
+  }
+
+  /// Returns the size of the table when none of its cells are truncated.
+  pub fn size(&self) -> Size {
+    let spacing = COLUMN_SPACING.saturating_mul(N.saturating_sub(1) as u16);
+
+    Size {
+      width: self.widths().into_iter().fold(spacing, u16::saturating_add),
+      height: u16::try_from(self.rows.len()).unwrap_or(u16::MAX),
+    }
   }
 
   fn aligned_rows(rows: Rows<'a, N>, alignments: &[Alignment; N], constraints: &[Constraint; N]) -> Vec<Row<'a>> {
@@ -68,6 +86,7 @@ impl<'a, const N: usize> Table<'a, N> {
     TuiTable::new(Self::aligned_rows(self.rows, &self.alignments, constraints))
       .highlight_style(Style::default().add_modifier(Modifier::BOLD))
       .widths(constraints)
+      .column_spacing(COLUMN_SPACING)
   }
 }
 
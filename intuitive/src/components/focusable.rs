@@ -0,0 +1,87 @@
+use crate::{
+  component,
+  components::children::Children,
+  element::{Any as AnyElement, Element},
+  event::{
+    handler::Propagate,
+    tree::{self, Route},
+    KeyEvent, KeyHandler,
+  },
+  focus::{self, Focus},
+  terminal::{Frame, Rect, Size},
+};
+
+/// A component that can receive focus.
+///
+/// While a `Focusable` is focused, key events are sent to its child, and unhandled key
+/// events bubble up to its `on_key` handler and its ancestors. Pressing
+/// `Tab` or `Shift+Tab` moves the focus between `Focusable`s, in the order in which they
+/// were rendered. Descendants of a `Focusable` can query whether it is focused with
+/// [`use_focus`]. For example,
+/// ```rust
+/// # use intuitive::{component, components::{Focusable, Section, Text, HStack}, focus::use_focus, render, style::Color};
+/// #
+/// #[component(Panel)]
+/// fn render(title: String) {
+///   let focus = use_focus();
+///   let border = if focus.is_focused() { Color::Blue } else { Color::Gray };
+///
+///   render! {
+///     Section(title, border)
+///   }
+/// }
+///
+/// #[component(Root)]
+/// fn render() {
+///   render! {
+///     HStack() {
+///       Focusable() { Panel(title: "Left") }
+///       Focusable() { Panel(title: "Right") }
+///     }
+///   }
+/// }
+/// ```
+///
+/// See the [`focus`] module documentation for details.
+///
+/// [`focus`]: ../focus/index.html
+/// [`use_focus`]: ../focus/fn.use_focus.html
+#[component(Focusable)]
+pub fn render(children: Children<1>, on_key: KeyHandler) {
+  let index = focus::enter();
+  let focus = focus::use_focus();
+
+  let content = children[0].render();
+
+  focus::exit(index, on_key.clone());
+
+  AnyElement::new(Frozen {
+    focus,
+    content,
+    on_key: on_key.clone(),
+  })
+}
+
+struct Frozen {
+  focus: Focus,
+  content: AnyElement,
+  on_key: KeyHandler,
+}
+
+impl Element for Frozen {
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
+  }
+
+  fn measure(&self, available: Size) -> Size {
+    self.content.measure(available)
+  }
+
+  fn draw(&self, rect: Rect, frame: &mut Frame) {
+    if self.focus.is_focused() {
+      tree::route(Route::Focus);
+    }
+
+    self.content.draw(rect, frame);
+  }
+}
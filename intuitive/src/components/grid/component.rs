@@ -0,0 +1,266 @@
+use super::{Cell, CellList};
+use crate::{
+  component,
+  components::{
+    children::List,
+    stack::{measure_auto, place, Flex, FlexList},
+  },
+  element::{Any as AnyElement, Element},
+  event::{handler::Propagate, KeyEvent, KeyHandler, MouseEvent, MouseHandler},
+  terminal::{Frame, Rect, Size},
+};
+
+/// A component that lays out its children in a grid.
+///
+/// The sizes of the rows and columns of the grid are given by `rows` and `cols`, using
+/// the same [`Flex`] values as the stacks. Each child is placed by the [`Cell`] in
+/// `cells` at the same index, and may span several rows and columns. Rows and columns
+/// without a [`Flex`] grow with a weight of 1, and the grid has as many rows and columns
+/// as needed to fit its cells. Rows and columns with a [`Flex::Auto`] take the largest
+/// size measured by the children placed only in that row or column. `row_gap` and
+/// `col_gap` insert empty space between rows and columns. For example,
+/// ```rust
+/// # use intuitive::{component, components::{stack::Flex::*, Grid, Section}, render};
+/// #
+/// #[component(Dashboard)]
+/// fn render() {
+///   render! {
+///     Grid(
+///       rows: [Block(3), Grow(1)],
+///       cols: [1, 2],
+///       cells: [(0, 0, 1, 2), (1, 0, 1, 1), (1, 1, 1, 1)],
+///       col_gap: 1,
+///     ) {
+///       Section(title: "Header")
+///       Section(title: "Sidebar")
+///       Section(title: "Content")
+///     }
+///   }
+/// }
+/// ```
+/// renders a header spanning both columns, above a sidebar and a wider content section.
+///
+/// Like the stacks, events that are not handled by the children of the grid bubble up to
+/// its `on_key` and `on_mouse` handlers, while `on_key_capture` and `on_mouse_capture`
+/// receive events before its children do, as described in [`Element`].
+///
+/// [`Cell`]: grid/struct.Cell.html
+/// [`Element`]: ../element/trait.Element.html
+/// [`Flex`]: stack/enum.Flex.html
+/// [`Flex::Auto`]: stack/enum.Flex.html#variant.Auto
+#[component(Grid)]
+pub fn render(
+  rows: FlexList,
+  cols: FlexList,
+  cells: CellList,
+  row_gap: u16,
+  col_gap: u16,
+  children: List,
+  on_key: KeyHandler,
+  on_key_capture: KeyHandler,
+  on_mouse: MouseHandler,
+  on_mouse_capture: MouseHandler,
+) {
+  let explicit = cells.iter().take(children.len());
+  let num_cols = explicit
+    .map(|cell| cell.col.saturating_add(cell.col_span) as usize)
+    .fold(cols.len().max(1), usize::max);
+  let cells = cells.for_children(children.len(), num_cols as u16);
+  let num_rows = cells
+    .iter()
+    .map(|cell| cell.row.saturating_add(cell.row_span) as usize)
+    .fold(rows.len(), usize::max);
+
+  AnyElement::new(Frozen {
+    rows: rows.for_children(num_rows),
+    cols: cols.for_children(num_cols),
+    cells,
+    row_gap: *row_gap,
+    col_gap: *col_gap,
+
+    children: children.render(),
+    on_key: on_key.clone(),
+    on_key_capture: on_key_capture.clone(),
+    on_mouse: on_mouse.clone(),
+    on_mouse_capture: on_mouse_capture.clone(),
+  })
+}
+
+struct Frozen {
+  rows: Vec<Flex>,
+  cols: Vec<Flex>,
+  cells: Vec<Cell>,
+  row_gap: u16,
+  col_gap: u16,
+
+  children: Vec<AnyElement>,
+  on_key: KeyHandler,
+  on_key_capture: KeyHandler,
+  on_mouse: MouseHandler,
+  on_mouse_capture: MouseHandler,
+}
+
+/// Returns the offset and size of the span of `span` tracks starting at `start`.
+fn span(tracks: &[(u16, u16)], start: u16, span: u16) -> (u16, u16) {
+  let spanned = tracks.get(start as usize..).unwrap_or_default();
+  let spanned = &spanned[..spanned.len().min(span as usize)];
+
+  match (spanned.first(), spanned.last()) {
+    (Some(&(first, _)), Some(&(last, size))) => (first, last + size - first),
+    _ => (0, 0),
+  }
+}
+
+impl Frozen {
+  /// Returns the largest size measured by the children placed only in the track at
+  /// `index`, where `track` returns the first track and span of a cell.
+  fn measure_track<M>(&self, index: usize, track: fn(&Cell) -> (u16, u16), measure: M) -> u16
+  where
+    M: Fn(&AnyElement, &Cell) -> u16,
+  {
+    self
+      .children
+      .iter()
+      .zip(&self.cells)
+      .filter(|(_, cell)| track(cell) == (index as u16, 1))
+      .map(|(child, cell)| measure(child, cell))
+      .max()
+      .unwrap_or_default()
+  }
+
+  fn layout(&self, rect: Rect) -> Vec<Rect> {
+    let cols = measure_auto(&self.cols, |col| {
+      self.measure_track(col, |cell| (cell.col, cell.col_span), |child, _| child.measure(rect.into()).width)
+    });
+    let cols = place(&cols, rect.x, rect.width, self.col_gap);
+
+    // rows are measured within the width of the columns their children are placed in
+    let rows = measure_auto(&self.rows, |row| {
+      self.measure_track(
+        row,
+        |cell| (cell.row, cell.row_span),
+        |child, cell| {
+          let (_, width) = span(&cols, cell.col, cell.col_span);
+          child.measure(Size::new(width, rect.height)).height
+        },
+      )
+    });
+    let rows = place(&rows, rect.y, rect.height, self.row_gap);
+
+    self
+      .cells
+      .iter()
+      .map(|cell| {
+        let (y, height) = span(&rows, cell.row, cell.row_span);
+        let (x, width) = span(&cols, cell.col, cell.col_span);
+
+        Rect { x, y, width, height }
+      })
+      .collect()
+  }
+}
+
+impl Element for Frozen {
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
+  }
+
+  fn on_key_capture(&self, event: KeyEvent) -> Propagate {
+    self.on_key_capture.handle(event)
+  }
+
+  fn on_mouse(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+    self.on_mouse.handle(event)
+  }
+
+  fn on_mouse_capture(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+    self.on_mouse_capture.handle(event)
+  }
+
+  fn draw(&self, rect: Rect, frame: &mut Frame) {
+    for (child, rect) in self.children.iter().zip(self.layout(rect)) {
+      // children spanning no tracks are not drawn
+      if rect.area() > 0 {
+        child.draw(rect, frame);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use crate::{
+    components::{stack::Flex::*, Any as AnyComponent, Grid, Text},
+    element::Any as AnyElement,
+    event::tree::{self, rect_at},
+    render,
+    terminal::Rect,
+  };
+
+  #[test]
+  #[serial]
+  fn cells_span_tracks() {
+    let grid: AnyComponent = render! {
+      Grid(rows: [Block(2), Grow(1)], cols: [1, 1], cells: [(0, 0, 1, 2), (1, 0, 1, 1), (1, 1, 1, 1)], row_gap: 1, col_gap: 2) {
+        Text(text: "header")
+        Text(text: "left")
+        Text(text: "right")
+      }
+    };
+
+    let element: AnyElement = grid.render();
+    tree::draw(&element, Rect::new(0, 0, 12, 10));
+
+    assert_eq!(rect_at(11, 0), Some(Rect::new(0, 0, 12, 2)));
+    assert_eq!(rect_at(0, 3), Some(Rect::new(0, 3, 5, 7)));
+    assert_eq!(rect_at(7, 9), Some(Rect::new(7, 3, 5, 7)));
+
+    // the gaps belong to the grid itself
+    assert_eq!(rect_at(0, 2), Some(Rect::new(0, 0, 12, 10)));
+    assert_eq!(rect_at(5, 5), Some(Rect::new(0, 0, 12, 10)));
+  }
+
+  #[test]
+  #[serial]
+  fn children_flow_into_cells() {
+    let grid: AnyComponent = render! {
+      Grid(cols: [1, 1], cells: [(0, 0, 1, 2)]) {
+        Text(text: "header")
+        Text(text: "a")
+        Text(text: "b")
+        Text(text: "c")
+      }
+    };
+
+    let element: AnyElement = grid.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 9));
+
+    assert_eq!(rect_at(0, 0), Some(Rect::new(0, 0, 10, 3)));
+    assert_eq!(rect_at(0, 3), Some(Rect::new(0, 3, 5, 3)));
+    assert_eq!(rect_at(5, 3), Some(Rect::new(5, 3, 5, 3)));
+    assert_eq!(rect_at(0, 6), Some(Rect::new(0, 6, 5, 3)));
+    assert_eq!(rect_at(5, 6), Some(Rect::new(0, 0, 10, 9)));
+  }
+
+  #[test]
+  #[serial]
+  fn auto_tracks_fit_children() {
+    let grid: AnyComponent = render! {
+      Grid(rows: [Auto, Grow(1)], cols: [Auto, Grow(1)]) {
+        Text(text: "abc")
+        Text(text: "d")
+        Text(text: "e\nf")
+        Text(text: "g")
+      }
+    };
+
+    let element: AnyElement = grid.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 10));
+
+    assert_eq!(rect_at(0, 0), Some(Rect::new(0, 0, 3, 1)));
+    assert_eq!(rect_at(3, 0), Some(Rect::new(3, 0, 7, 1)));
+    assert_eq!(rect_at(0, 1), Some(Rect::new(0, 1, 3, 9)));
+  }
+}
@@ -0,0 +1,99 @@
+//! Structures relating to the `Grid` component.
+
+pub(super) mod component;
+
+use std::ops::Deref;
+
+/// The placement of a child of a [`Grid`].
+///
+/// A `Cell` can be created from a `(row, col)` tuple, spanning a single row and column,
+/// or from a `(row, col, row_span, col_span)` tuple.
+///
+/// [`Grid`]: ../struct.Grid.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+  pub row: u16,
+  pub col: u16,
+  pub row_span: u16,
+  pub col_span: u16,
+}
+
+impl From<(u16, u16)> for Cell {
+  fn from((row, col): (u16, u16)) -> Self {
+    Self {
+      row,
+      col,
+      row_span: 1,
+      col_span: 1,
+    }
+  }
+}
+
+impl From<(u16, u16, u16, u16)> for Cell {
+  fn from((row, col, row_span, col_span): (u16, u16, u16, u16)) -> Self {
+    Self {
+      row,
+      col,
+      row_span,
+      col_span,
+    }
+  }
+}
+
+/// A list of [`Cell`]s, whose length is only known at runtime.
+///
+/// This struct exists in order to implement `From` for arrays and `Vec`s of anything that
+/// can be converted into a [`Cell`]. Children without a corresponding [`Cell`] are placed
+/// in single cells, in the order in which they are passed, starting after the last row
+/// used by a child with a [`Cell`].
+///
+/// [`Cell`]: struct.Cell.html
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct CellList {
+  cells: Vec<Cell>,
+}
+
+impl CellList {
+  /// Returns the [`Cell`]s of `n` children, in a grid with `cols` columns.
+  ///
+  /// [`Cell`]: struct.Cell.html
+  pub(crate) fn for_children(&self, n: usize, cols: u16) -> Vec<Cell> {
+    let mut cells: Vec<Cell> = self.cells.iter().take(n).copied().collect();
+
+    let mut next = cells.iter().map(|cell| cell.row.saturating_add(cell.row_span)).max().unwrap_or(0) as usize * cols as usize;
+    while cells.len() < n {
+      cells.push(Cell::from(((next / cols as usize) as u16, (next % cols as usize) as u16)));
+      next += 1;
+    }
+
+    cells
+  }
+}
+
+impl<C: Into<Cell>, const N: usize> From<[C; N]> for CellList {
+  fn from(cells: [C; N]) -> Self {
+    cells.into_iter().collect()
+  }
+}
+
+impl<C: Into<Cell>> From<Vec<C>> for CellList {
+  fn from(cells: Vec<C>) -> Self {
+    cells.into_iter().collect()
+  }
+}
+
+impl<C: Into<Cell>> FromIterator<C> for CellList {
+  fn from_iter<I: IntoIterator<Item = C>>(cells: I) -> Self {
+    Self {
+      cells: cells.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+impl Deref for CellList {
+  type Target = [Cell];
+
+  fn deref(&self) -> &Self::Target {
+    &self.cells
+  }
+}
@@ -0,0 +1,193 @@
+use std::{
+  collections::hash_map::DefaultHasher,
+  hash::{Hash, Hasher},
+};
+
+use crate::{component, components::children::Children, state};
+
+/// The key of a [`Keyed`] component, created from any value implementing [`Hash`].
+///
+/// [`Hash`]: https://doc.rust-lang.org/std/hash/trait.Hash.html
+/// [`Keyed`]: struct.Keyed.html
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Key(u64);
+
+impl<T: Hash> From<T> for Key {
+  fn from(key: T) -> Self {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+
+    Self(hasher.finish())
+  }
+}
+
+/// A component whose hooks, along with those of its descendants, are stored by its `key`.
+///
+/// Hooks are normally stored by the order in which they are called, so they must be called
+/// in the same order on every render, as described in [`use_state`]. Instead, the hooks
+/// called while rendering the child of a `Keyed` are stored by its `key`, and only need to be
+/// called in the same order as in the last render in which a `Keyed` with the same key was
+/// rendered. When no `Keyed` with that key is rendered, the values of the hooks are dropped,
+/// so rendering it again starts over with fresh state.
+///
+/// Keys only need to be unique among the `Keyed` components rendered by the same
+/// component, so two instances of a component rendering the same keys do not share any
+/// state. If several are rendered with the same key, they are told apart by the order in
+/// which they are rendered.
+///
+/// A `Keyed` is rarely used directly, as the children of the `if`, `match`, and `for`
+/// constructs of the [`render!`] macro are keyed implicitly, and any component in a
+/// [`render!`] macro can be given a `key` parameter:
+/// ```rust
+/// # use intuitive::{component, components::{Section, VStack}, render, state::use_state};
+/// #
+/// #[component(Counter)]
+/// fn render(title: String) {
+///   let count = use_state(|| 0);
+///
+///   render! {
+///     Section(title: format!("{}: {}", title, count.get()))
+///   }
+/// }
+///
+/// #[component(Root)]
+/// fn render(names: Vec<String>) {
+///   render! {
+///     VStack() {
+///       for name in names {
+///         Counter(key: name, title: name)
+///       }
+///     }
+///   }
+/// }
+/// ```
+///
+/// [`render!`]: ../macro.render.html
+/// [`use_state`]: ../state/fn.use_state.html
+#[component(Keyed)]
+pub fn render(key: Key, children: Children<1>) {
+  let _scope = state::key_scope(key.0);
+
+  children[0].render()
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use crate::{
+    component,
+    components::{Component, Empty, VStack},
+    render,
+    state::{self, use_ref, Ref},
+  };
+
+  #[test]
+  #[serial]
+  fn render_control_flow_keys_hooks() {
+    #[component(Counter)]
+    fn render(log: Ref<Vec<i32>>) {
+      let count = use_ref(|| 0);
+      count.mutate(|count| *count += 1);
+      log.mutate(|log| log.push(count.get()));
+
+      render! { Empty() }
+    }
+
+    #[component(Root)]
+    fn render(items: Vec<u8>, log: Ref<Vec<i32>>) {
+      render! {
+        VStack() {
+          if items.is_empty() {
+            Empty()
+          } else {
+            Counter(log: log.clone())
+          }
+
+          for item in items {
+            Counter(key: item, log: log.clone())
+          }
+
+          match items.first() {
+            Some(0) => Counter(log: log.clone()),
+            _ => Empty(),
+          }
+        }
+      }
+    }
+
+    state::reset();
+
+    let log = Ref::new(Vec::new());
+    let render = |items: Vec<u8>| {
+      log.set(Vec::new());
+      Root { items, log: log.clone() }.render();
+      state::render_done().unwrap();
+
+      log.get()
+    };
+
+    assert_eq!(render(vec![1, 2]), [1, 1, 1]);
+    assert_eq!(render(vec![2, 1]), [2, 2, 2]);
+    assert_eq!(render(vec![0]), [3, 1, 1]);
+    assert_eq!(render(vec![]), Vec::<i32>::new());
+    assert_eq!(render(vec![1]), [1, 1]);
+  }
+
+  #[test]
+  #[serial]
+  fn render_keys_hooks_per_component() {
+    #[component(Counter)]
+    fn render(log: Ref<Vec<(u8, i32)>>, id: u8) {
+      let count = use_ref(|| 0);
+      count.mutate(|count| *count += 1);
+      log.mutate(|log| log.push((*id, count.get())));
+
+      render! { Empty() }
+    }
+
+    #[component(List)]
+    fn render(items: Vec<u8>, log: Ref<Vec<(u8, i32)>>) {
+      render! {
+        VStack() {
+          for item in items {
+            Counter(key: item, id: *item, log: log.clone())
+          }
+        }
+      }
+    }
+
+    #[component(Root)]
+    fn render(first: Vec<u8>, second: Vec<u8>, log: Ref<Vec<(u8, i32)>>) {
+      render! {
+        VStack() {
+          List(items: first.clone(), log: log.clone())
+          List(items: second.clone(), log: log.clone())
+        }
+      }
+    }
+
+    state::reset();
+
+    let log = Ref::new(Vec::new());
+    let render = |first: Vec<u8>, second: Vec<u8>| {
+      log.set(Vec::new());
+      Root {
+        first,
+        second,
+        log: log.clone(),
+      }
+      .render();
+      state::render_done().unwrap();
+
+      log.get()
+    };
+
+    assert_eq!(render(vec![1], vec![1, 2]), [(1, 1), (1, 1), (2, 1)]);
+    assert_eq!(render(vec![1], vec![1, 2]), [(1, 2), (1, 2), (2, 2)]);
+
+    // the second list keeps its own counters when the first one empties
+    assert_eq!(render(vec![], vec![1, 2]), [(1, 3), (2, 3)]);
+    assert_eq!(render(vec![1], vec![1, 2]), [(1, 1), (1, 4), (2, 4)]);
+  }
+}
@@ -46,13 +46,13 @@
 //! Drawing a cursor requires us to implement a custom [`Element`],
 //! specifically so we can control the drawing of the cursor. Notice that
 //! we use a functional component to return a custom [`element::Any`], instead
-//! of returning a [`render!`] invocation.
+//! of returning a [`render!`] invocation. Key events reach the `Section` through the
+//! `Frozen` element, as described in [`Element`], so it does not handle them itself.
 //! ```rust
 //! # use intuitive::{
 //! #   component,
 //! #   components::{Section, Text},
 //! #   element::{Any as AnyElement, Element},
-//! #   event::KeyEvent,
 //! #   on_key, render,
 //! #   state::use_state,
 //! #   terminal::{Rect, Frame},
@@ -83,10 +83,6 @@
 //! }
 //!
 //! impl Element for Frozen {
-//!   fn on_key(&self, event: KeyEvent) {
-//!     self.content.on_key(event);
-//!   }
-//!
 //!   fn draw(&self, rect: Rect, frame: &mut Frame) {
 //!     self.content.draw(rect, frame);
 //!     frame.set_cursor(rect.x + self.cursor + 1, rect.y + 1);
@@ -95,76 +91,55 @@
 //! ```
 //!
 //! ## Focus
-//! In order to implement focusing on specific sections, we need to construct the components
-//! to be focused on, specifically the three `Input`s manually, when rendering our `Root` component.
-//! Notice that we also call [`Component::render`] on those `Input`s, because we want to be
-//! able to delegate key events to them, depending on which is focused. Lastly, we use [`Embed`]
-//! in order to make use of a rendered component inside of the [`render!`] macro.
+//! In order to implement focusing on specific sections, we wrap each of the components
+//! to be focused on, specifically the three `Input`s, in a [`Focusable`]. Pressing `Tab`
+//! or `Shift+Tab` then moves the focus between them, and key events are sent to the
+//! focused `Input` first. Key events that it does not handle, like `Esc` below, bubble up
+//! to the `on_key` handler of the `VStack`. Each `Input` calls [`use_focus`] in order to
+//! highlight its border when it is focused.
 //!
 //! ```rust
 //! # use intuitive::{
 //! #   component,
-//! #   components::{Section, Text, VStack, Embed},
-//! #   element::{Any as AnyElement, Element},
-//! #   event::KeyEvent,
+//! #   components::{Focusable, Section, Text, VStack},
+//! #   focus::use_focus,
 //! #   on_key, render,
 //! #   state::use_state,
 //! #   style::Color,
-//! #   terminal::{Rect, Frame},
 //! # };
 //! #
-//! #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-//! enum Focus {
-//!   A,
-//!   B,
-//!   C,
-//! }
-//!
 //! #[component(Input)]
-//! fn render(title: String, focused: bool) {
+//! fn render(title: String) {
 //!   let text = use_state(|| String::new());
+//!   let focus = use_focus();
 //!
-//!   let color = if *focused { Color::Blue } else { Color::Gray };
+//!   let color = if focus.is_focused() { Color::Blue } else { Color::Gray };
 //!
 //!   let on_key = on_key! { [text]
 //!     KeyEvent { code: Char(c), .. } => text.mutate(|text| text.push(c)),
 //!     KeyEvent { code: Backspace, .. } => text.mutate(|text| text.pop()),
+//!
+//!     _ => return Propagate::Next,
 //!   };
 //!
 //!   render! {
-//!     Section(title, border: color) {
-//!       Text(text: text.get(), on_key)
+//!     Section(title, border: color, on_key) {
+//!       Text(text: text.get())
 //!     }
 //!   }
 //! }
 //!
 //! #[component(Root)]
 //! fn render() {
-//!   let focus = use_state(|| Focus::A);
-//!
-//!   let input_a = Input::new("A".to_string(), focus.get() == Focus::A).render();
-//!   let input_b = Input::new("B".to_string(), focus.get() == Focus::B).render();
-//!   let input_c = Input::new("C".to_string(), focus.get() == Focus::C).render();
-//!
-//!   let on_key = on_key! { [focus, input_a, input_b, input_c]
-//!     KeyEvent { code: Tab, .. } => focus.update(|focus| match focus {
-//!       Focus::A => Focus::B,
-//!       Focus::B => Focus::C,
-//!       Focus::C => Focus::A,
-//!     }),
-//!
-//!     event if focus.get() == Focus::A => input_a.on_key(event),
-//!     event if focus.get() == Focus::B => input_b.on_key(event),
-//!     event if focus.get() == Focus::C => input_c.on_key(event),
-//!
+//!   let on_key = on_key! {
 //!     KeyEvent { code: Esc, .. } => event::quit(),
 //!   };
 //!
 //!   render! {
 //!     VStack(on_key) {
-//!       Embed(content: input_a)
-//!       Embed(content: input_b)
-//!       Embed(content: input_c)
+//!       Focusable() { Input(title: "A") }
+//!       Focusable() { Input(title: "B") }
+//!       Focusable() { Input(title: "C") }
 //!     }
 //!   }
 //! }
@@ -176,14 +151,17 @@
 //! [`Default`]: https://doc.rust-lang.org/std/default/trait.Default.html
 //! [`element::Any`]: ../element/struct.Any.html
 //! [`Element`]: ../element/trait.Element.html
-//! [`Embed`]: struct.Embed.html
 //! [Focus]: #focus
+//! [`Focusable`]: struct.Focusable.html
 //! [Input Box]: #input-box
 //! [Input Box With Cursor]: #input-box-with-cursor
 //! [`render!`]: ../macro.render.html
-//! [`Component::render`]: #tymethod.render
+//! [`use_focus`]: ../focus/fn.use_focus.html
 
+pub mod align;
 pub mod children;
+pub mod grid;
+pub mod param;
 pub mod stack;
 
 mod experimental_components;
@@ -202,14 +180,22 @@ mod any;
 mod centered;
 mod embed;
 mod empty;
+mod focusable;
+mod keyed;
+mod padding;
 mod section;
 mod text;
 
 pub use self::{
+  align::component::Align,
   any::Any,
   centered::Centered,
   embed::Embed,
   empty::Empty,
+  focusable::Focusable,
+  grid::component::Grid,
+  keyed::{Key, Keyed},
+  padding::Padding,
   section::Section,
   stack::{horizontal::Stack as HStack, vertical::Stack as VStack},
   text::Text,
@@ -236,13 +222,17 @@ use crate::element::Any as AnyElement;
 ///      same order.
 ///    - This can typically be guaranteed by always calling [`Component::render`]
 ///      on your component's children.
+///    - Memoized components, as created through [`use_memo`], skip calling
+///      [`Component::render`] on their children when they are unchanged. Their hooks are
+///      kept as if they were called in the same order, so this still holds.
 /// 2. [`Component::render`] must never be called outside of [`Component::render`]. This is to
 ///    continue the assurances made in the previous point.
 /// 3. Structures implementing `Component`, must also implement `Default`.
 /// 4. Structures implementing `Component` must have all of their fields public.
 /// 5. Structures implementing `Component` _should_ have an `on_key` parameter if they also
-///    take in `children`. This `on_key` parameter should be of type [`KeyHandler`] and
-///    should default to forwarding the key events to the children.
+///    take in `children`. This `on_key` parameter should be of type [`KeyHandler`], and
+///    receives the key events bubbling up from the children. Key events reach the
+///    children without being forwarded, as described in [`Element`].
 ///
 /// Refer to the [`Section` component source] as an example component that
 /// adheres to these invariants.
@@ -264,6 +254,7 @@ use crate::element::Any as AnyElement;
 /// [`Component::render`]: #tymethod.render
 /// [`Element`]: ../element/trait.Element.html
 /// [`KeyHandler`]: ../event/struct.KeyHandler.html
+/// [`use_memo`]: ../state/fn.use_memo.html
 /// [`use_state`]: ../state/fn.use_state.html
 /// [`Section` component source]: ../../src/intuitive/components/section.rs.html
 /// [`Children<N>`]: children/struct.Children.html
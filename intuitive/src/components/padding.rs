@@ -0,0 +1,150 @@
+use crate::{
+  component,
+  components::children::Children,
+  element::{Any as AnyElement, Element},
+  event::{handler::Propagate, KeyEvent, KeyHandler, MouseEvent, MouseHandler},
+  terminal::{Frame, Rect, Size},
+};
+
+/// A component for surrounding its contents with empty space.
+///
+/// `top`, `right`, `bottom`, and `left` are the number of empty rows or columns on each
+/// side of the contents, which shrink to fit when the padding does not. For example,
+/// ```rust
+/// # use intuitive::{component, components::{Padding, Section, Text}, render};
+/// #
+/// #[component(Root)]
+/// fn render() {
+///   render! {
+///     Section(title: "Padded") {
+///       Padding(top: 1, left: 2, right: 2) {
+///         Text(text: "Hi there!")
+///       }
+///     }
+///   }
+/// }
+/// ```
+/// renders the text one row below the border of the section, and two columns away from
+/// its sides.
+///
+/// Events that are not handled by the contents bubble up to the `on_key` and `on_mouse`
+/// handlers, while `on_key_capture` and `on_mouse_capture` receive events before the
+/// contents do, as described in [`Element`].
+///
+/// [`Element`]: ../element/trait.Element.html
+#[component(Padding)]
+pub fn render(
+  top: u16,
+  right: u16,
+  bottom: u16,
+  left: u16,
+  children: Children<1>,
+  on_key: KeyHandler,
+  on_key_capture: KeyHandler,
+  on_mouse: MouseHandler,
+  on_mouse_capture: MouseHandler,
+) {
+  AnyElement::new(Frozen {
+    top: *top,
+    right: *right,
+    bottom: *bottom,
+    left: *left,
+
+    content: children[0].render(),
+    on_key: on_key.clone(),
+    on_key_capture: on_key_capture.clone(),
+    on_mouse: on_mouse.clone(),
+    on_mouse_capture: on_mouse_capture.clone(),
+  })
+}
+
+struct Frozen {
+  top: u16,
+  right: u16,
+  bottom: u16,
+  left: u16,
+
+  content: AnyElement,
+  on_key: KeyHandler,
+  on_key_capture: KeyHandler,
+  on_mouse: MouseHandler,
+  on_mouse_capture: MouseHandler,
+}
+
+impl Frozen {
+  fn inner(&self, rect: Rect) -> Rect {
+    let left = self.left.min(rect.width);
+    let top = self.top.min(rect.height);
+
+    Rect {
+      x: rect.x + left,
+      y: rect.y + top,
+      width: (rect.width - left).saturating_sub(self.right),
+      height: (rect.height - top).saturating_sub(self.bottom),
+    }
+  }
+}
+
+impl Element for Frozen {
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
+  }
+
+  fn on_key_capture(&self, event: KeyEvent) -> Propagate {
+    self.on_key_capture.handle(event)
+  }
+
+  fn on_mouse(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+    self.on_mouse.handle(event)
+  }
+
+  fn on_mouse_capture(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+    self.on_mouse_capture.handle(event)
+  }
+
+  fn measure(&self, available: Size) -> Size {
+    let inner = self.inner(Rect::new(0, 0, available.width, available.height));
+    let content = self.content.measure(inner.into());
+
+    Size {
+      width: content.width.saturating_add(self.left).saturating_add(self.right),
+      height: content.height.saturating_add(self.top).saturating_add(self.bottom),
+    }
+    .min(available)
+  }
+
+  fn draw(&self, rect: Rect, frame: &mut Frame) {
+    self.content.draw(self.inner(rect), frame);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use crate::{
+    components::{Any as AnyComponent, Padding, Text},
+    element::Any as AnyElement,
+    event::tree::{self, rect_at},
+    render,
+    terminal::Rect,
+  };
+
+  #[test]
+  #[serial]
+  fn padding_shrinks_contents() {
+    let padding: AnyComponent = render! {
+      Padding(top: 1, right: 2, bottom: 3, left: 4) {
+        Text(text: "a")
+      }
+    };
+
+    let element: AnyElement = padding.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 10));
+    assert_eq!(rect_at(4, 1), Some(Rect::new(4, 1, 4, 6)));
+    assert_eq!(rect_at(0, 0), Some(Rect::new(0, 0, 10, 10)));
+
+    tree::draw(&element, Rect::new(0, 0, 3, 2));
+    assert_eq!(rect_at(2, 1), Some(Rect::new(0, 0, 3, 2)));
+  }
+}
@@ -0,0 +1,235 @@
+//! Structures for converting the parameters of components.
+//!
+//! Parameters passed in a [`render!`] macro are converted through [`FromParam`], which
+//! converts any value implementing [`Into`] the type of the parameter. Optional parameters
+//! of components created with the [`component` attribute macro] are stored as an
+//! [`Optional`], which can additionally be created from any value implementing [`Into`] the
+//! type it wraps, so that these values do not need to be wrapped in `Some` when passed.
+//! Numeric literals without a suffix are converted through [`from_literal`], which infers
+//! their type from the parameter.
+//!
+//! [`component` attribute macro]: ../../attr.component.html
+//! [`from_literal`]: fn.from_literal.html
+//! [`FromParam`]: trait.FromParam.html
+//! [`Into`]: https://doc.rust-lang.org/std/convert/trait.Into.html
+//! [`Optional`]: struct.Optional.html
+//! [`render!`]: ../../macro.render.html
+
+use std::ops::Deref;
+
+/// An optional parameter of a component.
+///
+/// Components created with the [`component` attribute macro] store parameters of type
+/// `Option<T>` as an `Optional<T>`, which is passed to their `render` function as an
+/// `Option<T>`. When omitted, optional parameters are `None`.
+///
+/// [`component` attribute macro]: ../../attr.component.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Optional<T>(pub Option<T>);
+
+impl<T> Default for Optional<T> {
+  fn default() -> Self {
+    Self(None)
+  }
+}
+
+impl<T> Deref for Optional<T> {
+  type Target = Option<T>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl<T> From<Option<T>> for Optional<T> {
+  fn from(option: Option<T>) -> Self {
+    Self(option)
+  }
+}
+
+impl<T> From<Optional<T>> for Option<T> {
+  fn from(optional: Optional<T>) -> Self {
+    optional.0
+  }
+}
+
+/// Marks the [`FromParam`] implementation for values implementing [`Into`] the parameter.
+///
+/// [`FromParam`]: trait.FromParam.html
+/// [`Into`]: https://doc.rust-lang.org/std/convert/trait.Into.html
+pub struct Convert;
+
+/// Marks the [`FromParam`] implementation for values wrapped in an [`Optional`].
+///
+/// [`FromParam`]: trait.FromParam.html
+/// [`Optional`]: struct.Optional.html
+pub struct Wrap;
+
+/// Conversion of a value passed as a parameter in a [`render!`] macro.
+///
+/// The marker `M` is either [`Convert`] or [`Wrap`], and is always inferred. Since a
+/// value can not implement [`Into`] both an [`Optional<T>`] and `T`, at most one of the
+/// implementations applies to any parameter.
+///
+/// [`Convert`]: struct.Convert.html
+/// [`Into`]: https://doc.rust-lang.org/std/convert/trait.Into.html
+/// [`Optional<T>`]: struct.Optional.html
+/// [`render!`]: ../../macro.render.html
+/// [`Wrap`]: struct.Wrap.html
+pub trait FromParam<V, M> {
+  fn from_param(value: V) -> Self;
+}
+
+impl<T, V: Into<T>> FromParam<V, Convert> for T {
+  fn from_param(value: V) -> Self {
+    value.into()
+  }
+}
+
+impl<T, V: Into<T>> FromParam<V, Wrap> for Optional<T> {
+  fn from_param(value: V) -> Self {
+    Self(Some(value.into()))
+  }
+}
+
+/// The type of the numeric literals that can be passed as a parameter of type `Self`.
+///
+/// Numeric literals without a suffix, such as `5`, are passed as a `Self::Value` through
+/// [`from_literal`], since their type would otherwise default to `i32` before being
+/// converted through [`FromParam`]. This is implemented for the numeric types, any
+/// `Option` or [`Optional`] of them, and parameter types created from numbers, such as
+/// [`Length`].
+/// Literals passed as parameters of other types need a suffix, as in `5u16`, so that they
+/// are converted through [`FromParam`] instead.
+///
+/// [`from_literal`]: fn.from_literal.html
+/// [`FromParam`]: trait.FromParam.html
+/// [`Length`]: ../align/enum.Length.html
+/// [`Optional`]: struct.Optional.html
+pub trait Literal {
+  type Value;
+}
+
+macro_rules! impl_literal {
+  ($($ty:ty),*) => {
+    $(
+      impl Literal for $ty {
+        type Value = $ty;
+      }
+    )*
+  };
+}
+
+impl_literal!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl<T: Literal> Literal for Option<T> {
+  type Value = T::Value;
+}
+
+impl<T: Literal> Literal for Optional<T> {
+  type Value = T::Value;
+}
+
+/// Converts a numeric literal into a parameter of type `T` through [`FromParam`], inferring
+/// the type of the literal from `T`.
+///
+/// This is used by the [`render!`] macro for numeric literals without a suffix.
+///
+/// [`FromParam`]: trait.FromParam.html
+/// [`render!`]: ../../macro.render.html
+pub fn from_literal<T, M>(value: T::Value) -> T
+where
+  T: Literal + FromParam<T::Value, M>,
+{
+  T::from_param(value)
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use super::*;
+  use crate::{
+    component,
+    components::{Any as AnyComponent, Empty, Text},
+    render,
+    state::Ref,
+    style::{Color, Style},
+  };
+
+  /// The label, whether the border is gray, the title, and the width.
+  type Seen = (String, bool, Option<String>, Option<u16>);
+
+  #[component(Labeled)]
+  fn render(
+    #[default("label")] label: String,
+    #[default(Color::Gray)] border: Style,
+    title: Option<String>,
+    width: Option<u16>,
+    seen: Ref<Option<Seen>>,
+  ) {
+    let gray = *border == Style::from(Color::Gray);
+    seen.set(Some((label.clone(), gray, title.clone(), *width)));
+
+    render! { Empty() }
+  }
+
+  #[component(Counted)]
+  fn render(#[default(5)] count: u16, #[default(5)] limit: Option<u16>) {
+    render! { Text(text: format!("{} of {:?}", count, limit)) }
+  }
+
+  fn convert<T: FromParam<V, M>, V, M>(value: V) -> T {
+    T::from_param(value)
+  }
+
+  #[test]
+  fn optional_params_wrap_values() {
+    let title: Optional<String> = convert("title");
+    assert_eq!(title, Optional(Some(String::from("title"))));
+
+    let title: Optional<String> = convert(String::from("title"));
+    assert_eq!(*title, Some(String::from("title")));
+
+    let title: Optional<String> = convert(None);
+    assert_eq!(*title, None);
+
+    let width: Optional<u16> = convert(Some(5));
+    assert_eq!(*width, Some(5));
+
+    let text: String = convert("text");
+    assert_eq!(text, "text");
+  }
+
+  #[test]
+  fn defaults_accept_unsuffixed_literals() {
+    let counted = Counted::default();
+    assert_eq!(counted.count, 5);
+    assert_eq!(*counted.limit, Some(5));
+  }
+
+  #[test]
+  #[serial]
+  fn component_params_have_defaults() {
+    let labeled = Labeled::default();
+    assert_eq!(labeled.label, "label");
+    assert!(labeled.border == Style::from(Color::Gray));
+    assert_eq!(*labeled.title, None);
+
+    let seen = Ref::new(None);
+    let labeled: AnyComponent = render! { Labeled(title: "title", width: Some(5), seen: seen.clone()) };
+    labeled.render();
+    assert_eq!(
+      seen.get(),
+      Some((String::from("label"), true, Some(String::from("title")), Some(5)))
+    );
+
+    let labeled: AnyComponent = render! { Labeled(label: "other", seen: seen.clone()) };
+    labeled.render();
+    assert_eq!(seen.get(), Some((String::from("other"), true, None, None)));
+
+    let labeled: AnyComponent = render! { Labeled(width: 5, seen: seen.clone()) };
+    labeled.render();
+    assert_eq!(seen.get(), Some((String::from("label"), true, None, Some(5))));
+  }
+}
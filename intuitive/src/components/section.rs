@@ -7,9 +7,9 @@ use crate::{
   component,
   components::children::Children,
   element::{Any as AnyElement, Element},
-  event::{KeyEvent, KeyHandler, MouseEvent, MouseHandler},
+  event::{handler::Propagate, Gesture, GestureHandler, KeyEvent, KeyHandler, MouseEvent, MouseHandler, PasteHandler},
   style::Style,
-  terminal::{Frame, Rect},
+  terminal::{Frame, Rect, Size},
   text::Spans,
 };
 
@@ -36,16 +36,42 @@ use crate::{
 /// `Section` also accepts a border [`Style`]. This style will merge with any style applied to
 /// the title.
 ///
+/// Key events, mouse events, pasted text, and [`Gesture`]s that are not handled by the
+/// content of a `Section` bubble up to its `on_key`, `on_mouse`, `on_paste`, and
+/// `on_gesture` handlers. Clicks are also sent to `on_click`, unless `on_gesture` handled
+/// them. The `on_key_capture`, `on_mouse_capture`, and `on_paste_capture` handlers receive
+/// events before the content of the `Section` does, as described in [`Element`].
+///
+/// [`Element`]: ../element/trait.Element.html
+/// [`Gesture`]: ../event/enum.Gesture.html
 /// [`Style`]: ../style/struct.Style.html
 #[component(Section)]
-pub fn render(title: Spans, border: Style, children: Children<1>, on_key: KeyHandler, on_mouse: MouseHandler) {
+pub fn render(
+  title: Spans,
+  border: Style,
+  children: Children<1>,
+  on_key: KeyHandler,
+  on_key_capture: KeyHandler,
+  on_mouse: MouseHandler,
+  on_mouse_capture: MouseHandler,
+  on_paste: PasteHandler,
+  on_paste_capture: PasteHandler,
+  on_click: MouseHandler,
+  on_gesture: GestureHandler,
+) {
   AnyElement::new(Frozen {
     title: title.clone(),
     border: *border,
 
     content: children[0].render(),
     on_key: on_key.clone(),
+    on_key_capture: on_key_capture.clone(),
     on_mouse: on_mouse.clone(),
+    on_mouse_capture: on_mouse_capture.clone(),
+    on_paste: on_paste.clone(),
+    on_paste_capture: on_paste_capture.clone(),
+    on_click: on_click.clone(),
+    on_gesture: on_gesture.clone(),
   })
 }
 
@@ -55,26 +81,57 @@ struct Frozen {
 
   content: AnyElement,
   on_key: KeyHandler,
+  on_key_capture: KeyHandler,
   on_mouse: MouseHandler,
+  on_mouse_capture: MouseHandler,
+  on_paste: PasteHandler,
+  on_paste_capture: PasteHandler,
+  on_click: MouseHandler,
+  on_gesture: GestureHandler,
 }
 
 impl Element for Frozen {
-  fn on_key(&self, event: KeyEvent) {
-    self.on_key.handle_or(event, |event| self.content.on_key(event));
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
+  }
+
+  fn on_key_capture(&self, event: KeyEvent) -> Propagate {
+    self.on_key_capture.handle(event)
+  }
+
+  fn on_mouse(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+    self.on_mouse.handle(event)
   }
 
-  fn on_mouse(&self, rect: Rect, event: MouseEvent) {
-    self.on_mouse.handle_or(event, |event| {
-      self.content.on_mouse(
-        Rect {
-          x: rect.x + 1,
-          y: rect.y - 1,
-          width: rect.width - 1,
-          height: rect.height - 1,
-        },
-        event,
-      );
-    });
+  fn on_mouse_capture(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+    self.on_mouse_capture.handle(event)
+  }
+
+  fn on_paste(&self, text: &str) -> Propagate {
+    self.on_paste.handle(text.to_string())
+  }
+
+  fn on_paste_capture(&self, text: &str) -> Propagate {
+    self.on_paste_capture.handle(text.to_string())
+  }
+
+  fn on_gesture(&self, _rect: Rect, gesture: Gesture) -> Propagate {
+    self.on_gesture.handle_or(gesture, |gesture| match gesture {
+      Gesture::Click(event) => self.on_click.handle(event),
+      _ => Propagate::Next,
+    })
+  }
+
+  fn measure(&self, available: Size) -> Size {
+    let inner = Size::new(available.width.saturating_sub(2), available.height.saturating_sub(2));
+    let content = self.content.measure(inner);
+    let title = u16::try_from(TuiSpans::from(&self.title).width()).unwrap_or(u16::MAX);
+
+    Size {
+      width: content.width.max(title).saturating_add(2),
+      height: content.height.saturating_add(2),
+    }
+    .min(available)
   }
 
   fn draw(&self, rect: Rect, frame: &mut Frame) {
@@ -1,4 +1,7 @@
-use std::ops::Deref;
+use std::{
+  cmp::{Ordering, Reverse},
+  ops::Deref,
+};
 
 /// Control how much each child of a stack component grows.
 ///
@@ -24,7 +27,8 @@ use std::ops::Deref;
 /// When using the `flex` parameter to [`VStack`] and [`HStack`], providing a value
 /// of type `[u16; N]`, will assume that [`Flex::Grow`] is intended, therefore making
 /// all dimensions relative. In order to have absolute height or width for a child, provide a
-/// value of type `[Flex; N]` to the `flex` parameter. For example,
+/// value of type `[Flex; N]` to the `flex` parameter. When the number of children is only
+/// known at runtime, a `Vec` of either can be provided instead. For example,
 /// ```rust
 /// # use intuitive::{component, components::{Section, VStack, stack::Flex::*}, render};
 /// #
@@ -40,25 +44,263 @@ use std::ops::Deref;
 /// }
 /// ```
 ///
+/// # Layout
+/// Children with a [`Flex::Block`], [`Flex::Percentage`], or [`Flex::Ratio`] have a fixed
+/// size. The remaining space is split between the other children in proportion to their
+/// weights, where [`Flex::Fill`], [`Flex::Min`], and [`Flex::Max`] have a weight of 1. Children whose share
+/// is smaller than their minimum or larger than their maximum are given that size instead,
+/// and the rest of the space is split between the others.
+///
+/// Cells left over from rounding are given to the children with the largest fractional
+/// parts, and to earlier children on ties, so the same constraints always produce the
+/// same layout. If the children are given all of the available space, they fill the
+/// stack exactly. Space is left over at the end of the stack only if there are no growing
+/// children, or if they have all reached their maximum size.
+///
+/// Children with a [`Flex::Auto`] are given the size they would like to be, as reported
+/// by [`Element::measure`], and are then treated like a [`Flex::Block`] of that size.
+/// This sizes them to their content, such as the lines of a [`Text`].
+///
+/// If the fixed and minimum sizes do not fit, children are given their sizes in order, so
+/// that the last children are shrunk or left empty.
+///
+/// For example, a sidebar that takes up a third of the screen, but is never narrower than
+/// 20 columns or wider than 40, next to a panel that takes up a quarter of the screen:
+/// ```rust
+/// # use intuitive::{component, components::{Section, HStack, stack::Flex::*}, render};
+/// #
+/// #[component(Root)]
+/// fn render() {
+///   render! {
+///     HStack(flex: [Bounded { grow: 1, min: 20, max: 40 }, Grow(2), Percentage(25)]) {
+///       Section(title: "sidebar")
+///       Section(title: "main")
+///       Section(title: "panel")
+///     }
+///   }
+/// }
+/// ```
+///
+/// [`Element::measure`]: ../../element/trait.Element.html#method.measure
+/// [`Flex::Auto`]: #variant.Auto
+/// [`Flex::Block`]: #variant.Block
+/// [`Flex::Bounded`]: #variant.Bounded
+/// [`Flex::Fill`]: #variant.Fill
+/// [`Flex::Grow`]: #variant.Grow
+/// [`Flex::Max`]: #variant.Max
+/// [`Flex::Min`]: #variant.Min
+/// [`Flex::Percentage`]: #variant.Percentage
+/// [`Flex::Ratio`]: #variant.Ratio
 /// [`HStack`]: ../struct.HStack.html
 /// [`Section`]: ../struct.Section.html
+/// [`Text`]: ../struct.Text.html
 /// [`VStack`]: ../struct.VStack.html
-/// [`Flex::Grow`]: #variant.Grow
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Flex {
   /// An absolute amount of height or width.
   Block(u16),
   /// A relative amount of height or width.
   Grow(u16),
+  /// A share of the remaining height or width, the same as `Grow(1)`.
+  Fill,
+  /// At least this amount of height or width, growing with a weight of 1.
+  Min(u16),
+  /// At most this amount of height or width, growing with a weight of 1.
+  Max(u16),
+  /// A relative amount of height or width, between `min` and `max` inclusive.
+  Bounded { grow: u16, min: u16, max: u16 },
+  /// A percentage of the available height or width, up to 100.
+  Percentage(u16),
+  /// A fraction of the available height or width, up to 1.
+  Ratio(u32, u32),
+  /// The height or width measured by the child.
+  Auto,
+}
+
+/// The precision with which fractional sizes are computed, in parts of a cell.
+const SCALE: u64 = 1 << 16;
+
+impl Flex {
+  /// Returns the size of a fixed constraint in `1 / SCALE`s of a cell, or `None` if the
+  /// constraint grows.
+  fn fixed(self, available: u16) -> Option<u64> {
+    let available = u64::from(available);
+
+    match self {
+      Self::Block(size) => Some(u64::from(size) * SCALE),
+      Self::Percentage(percent) => Some(available * u64::from(percent.min(100)) * SCALE / 100),
+      Self::Ratio(_, 0) => Some(0),
+      Self::Ratio(num, den) => Some(available * u64::from(num.min(den)) * SCALE / u64::from(den)),
+      // children are measured before solving, so an unmeasured child takes no space
+      Self::Auto => Some(0),
+
+      Self::Grow(_) | Self::Fill | Self::Min(_) | Self::Max(_) | Self::Bounded { .. } => None,
+    }
+  }
+
+  /// Returns the weight, minimum, and maximum sizes of a growing constraint.
+  fn bounds(self) -> (u64, u16, u16) {
+    match self {
+      Self::Grow(grow) => (u64::from(grow), 0, u16::MAX),
+      Self::Fill => (1, 0, u16::MAX),
+      Self::Min(min) => (1, min, u16::MAX),
+      Self::Max(max) => (1, 0, max),
+      Self::Bounded { grow, min, max } => (u64::from(grow), min, max.max(min)),
+
+      Self::Block(_) | Self::Percentage(_) | Self::Ratio(..) | Self::Auto => (0, 0, 0),
+    }
+  }
+}
+
+/// Replaces each [`Flex::Auto`] with a [`Flex::Block`] of the size returned by `measure`
+/// for the child at its index.
+///
+/// [`Flex::Auto`]: enum.Flex.html#variant.Auto
+/// [`Flex::Block`]: enum.Flex.html#variant.Block
+pub(crate) fn measure_auto(flex: &[Flex], mut measure: impl FnMut(usize) -> u16) -> Vec<Flex> {
+  flex
+    .iter()
+    .enumerate()
+    .map(|(i, flex)| match flex {
+      Flex::Auto => Flex::Block(measure(i)),
+      flex => *flex,
+    })
+    .collect()
+}
+
+/// Returns the offset and size of each child, when children with the given constraints
+/// are laid out from `start` in `available` cells, with `gap` cells between them.
+///
+/// When the gaps do not fit, the children that would start past the end are placed at
+/// the end, with a size of zero.
+pub(crate) fn place(flex: &[Flex], start: u16, available: u16, gap: u16) -> Vec<(u16, u16)> {
+  let gaps = gap.saturating_mul(flex.len().saturating_sub(1) as u16);
+  let end = start.saturating_add(available);
+  let mut offset = start;
+
+  solve(flex, available.saturating_sub(gaps))
+    .into_iter()
+    .map(|size| {
+      let child = (offset, size.min(end - offset));
+      offset = offset.saturating_add(size).saturating_add(gap).min(end);
+
+      child
+    })
+    .collect()
+}
+
+/// Splits `available` cells between children with the given constraints, as described
+/// in the [`Flex`] docs.
+///
+/// [`Flex`]: enum.Flex.html
+pub(crate) fn solve(flex: &[Flex], available: u16) -> Vec<u16> {
+  let fixed: Vec<Option<u64>> = flex.iter().map(|flex| flex.fixed(available)).collect();
+
+  let mut sizes: Vec<u16> = flex
+    .iter()
+    .zip(&fixed)
+    .map(|(flex, fixed)| match fixed {
+      Some(fixed) => u16::try_from(fixed / SCALE).unwrap_or(u16::MAX),
+      None => flex.bounds().1,
+    })
+    .collect();
+
+  // cells lost when rounding down fixed sizes go to the largest fractional parts, where
+  // each fixed size is at most one part of a cell smaller than its exact size
+  let exact: u64 = (fixed.iter().flatten().sum::<u64>() + fixed.iter().flatten().count() as u64) / SCALE;
+  let floored: u64 = fixed.iter().flatten().map(|fixed| fixed / SCALE).sum();
+  let mut fractional: Vec<usize> = (0..flex.len()).filter(|&i| fixed[i].is_some()).collect();
+  fractional.sort_by_key(|&i| (Reverse(fixed[i].unwrap_or_default() % SCALE), i));
+  for &i in fractional.iter().take((exact - floored) as usize) {
+    sizes[i] += 1;
+  }
+
+  let required: u64 = sizes.iter().map(|&size| u64::from(size)).sum();
+  if required > u64::from(available) {
+    let mut remaining = available;
+    for size in &mut sizes {
+      *size = (*size).min(remaining);
+      remaining -= *size;
+    }
+
+    return sizes;
+  }
+
+  let fixed_size: u64 = (0..flex.len()).filter(|&i| fixed[i].is_some()).map(|i| u64::from(sizes[i])).sum();
+
+  let mut remaining = u64::from(available) - fixed_size;
+  let mut growing: Vec<usize> = (0..flex.len()).filter(|&i| fixed[i].is_none()).collect();
+
+  // growing children whose share falls outside of their bounds are clamped, and the
+  // space is split again between the others, similarly to css flex items
+  loop {
+    let total = i128::from(growing.iter().map(|&i| flex[i].bounds().0).sum::<u64>());
+
+    let mut violation: i128 = 0;
+    let mut min_violations = Vec::new();
+    let mut max_violations = Vec::new();
+    for &i in &growing {
+      let (grow, min, max) = flex[i].bounds();
+      let share = i128::from(remaining * grow);
+
+      if total == 0 || share < i128::from(min) * total {
+        violation += i128::from(min) * total - share;
+        min_violations.push((i, min));
+      } else if share > i128::from(max) * total {
+        violation += i128::from(max) * total - share;
+        max_violations.push((i, max));
+      }
+    }
+
+    if min_violations.is_empty() && max_violations.is_empty() {
+      break;
+    }
+
+    // clamping children changes the space left for the others, so only the children
+    // violating their bounds in the direction of the total violation are clamped
+    let clamped = match violation.cmp(&0) {
+      Ordering::Greater => min_violations,
+      Ordering::Less => max_violations,
+      Ordering::Equal => [min_violations, max_violations].concat(),
+    };
+
+    for (i, size) in clamped {
+      sizes[i] = size;
+      remaining -= u64::from(size);
+      growing.retain(|&j| j != i);
+    }
+  }
+
+  // children with no weight were clamped to their minimum above, so `total` is not zero
+  // unless there are no growing children left
+  let total: u64 = growing.iter().map(|&i| flex[i].bounds().0).sum();
+  let mut shares: Vec<(usize, u64)> = Vec::new();
+  let mut left = remaining;
+  for &i in &growing {
+    let grow = flex[i].bounds().0;
+
+    sizes[i] = (remaining * grow / total) as u16;
+    left -= remaining * grow / total;
+    shares.push((i, remaining * grow % total));
+  }
+
+  // cells left over from rounding down go to the largest remainders
+  shares.sort_by_key(|&(i, share)| (Reverse(share), i));
+  for &(i, _) in shares.iter().take(left as usize) {
+    sizes[i] += 1;
+  }
+
+  sizes
 }
 
 /// An array of [`Flex`] values.
 ///
 /// This struct exists in order to implement `From<[Flex; N]>` and
-/// `From<[u16; N]>`.
+/// `From<[u16; N]>`. Stacks take a [`FlexList`], which can be created from an `Array`.
 ///
 /// [`Flex`]: enum.Flex.html
-#[derive(Clone, Copy)]
+/// [`FlexList`]: struct.FlexList.html
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Array<const N: usize> {
   flex: [Flex; N],
 }
@@ -90,3 +332,119 @@ impl<const N: usize> Default for Array<N> {
     Self { flex: [Flex::Grow(1); N] }
   }
 }
+
+/// A list of [`Flex`] values, whose length is only known at runtime.
+///
+/// This struct exists in order to implement `From` for arrays and `Vec`s of both [`Flex`]
+/// and `u16`, where a `u16` is a [`Flex::Grow`]. Children without a corresponding
+/// [`Flex`] grow with a weight of 1, and extra values are ignored.
+///
+/// [`Flex`]: enum.Flex.html
+/// [`Flex::Grow`]: enum.Flex.html#variant.Grow
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct List {
+  flex: Vec<Flex>,
+}
+
+impl List {
+  /// Returns the [`Flex`] values of `n` children.
+  ///
+  /// [`Flex`]: enum.Flex.html
+  pub(crate) fn for_children(&self, n: usize) -> Vec<Flex> {
+    (0..n).map(|i| self.flex.get(i).copied().unwrap_or(Flex::Grow(1))).collect()
+  }
+}
+
+impl<const N: usize> From<[u16; N]> for List {
+  fn from(flex: [u16; N]) -> Self {
+    flex.into_iter().map(Flex::Grow).collect()
+  }
+}
+
+impl<const N: usize> From<[Flex; N]> for List {
+  fn from(flex: [Flex; N]) -> Self {
+    Self { flex: flex.into() }
+  }
+}
+
+impl From<Vec<u16>> for List {
+  fn from(flex: Vec<u16>) -> Self {
+    flex.into_iter().map(Flex::Grow).collect()
+  }
+}
+
+impl From<Vec<Flex>> for List {
+  fn from(flex: Vec<Flex>) -> Self {
+    Self { flex }
+  }
+}
+
+impl<const N: usize> From<Array<N>> for List {
+  fn from(flex: Array<N>) -> Self {
+    Self { flex: flex.flex.into() }
+  }
+}
+
+impl FromIterator<Flex> for List {
+  fn from_iter<I: IntoIterator<Item = Flex>>(flex: I) -> Self {
+    Self {
+      flex: flex.into_iter().collect(),
+    }
+  }
+}
+
+impl Deref for List {
+  type Target = [Flex];
+
+  fn deref(&self) -> &Self::Target {
+    &self.flex
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Flex::*, *};
+
+  #[test]
+  fn grow_fills_exactly() {
+    assert_eq!(solve(&[Grow(1), Grow(1), Grow(1)], 10), vec![4, 3, 3]);
+    assert_eq!(solve(&[Grow(1), Grow(2)], 10), vec![3, 7]);
+    assert_eq!(solve(&[Block(3), Grow(1), Block(3)], 11), vec![3, 5, 3]);
+    assert_eq!(solve(&[Grow(0), Grow(0)], 10), vec![0, 0]);
+    assert_eq!(solve(&[Fill, Grow(1), Block(2)], 10), vec![4, 4, 2]);
+  }
+
+  #[test]
+  fn fixed_sizes() {
+    assert_eq!(solve(&[Percentage(50), Percentage(50)], 11), vec![6, 5]);
+    assert_eq!(solve(&[Ratio(1, 3), Ratio(1, 3), Ratio(1, 3)], 10), vec![4, 3, 3]);
+    assert_eq!(solve(&[Percentage(25), Grow(1)], 10), vec![2, 8]);
+    assert_eq!(solve(&[Percentage(200), Ratio(1, 0)], 10), vec![10, 0]);
+
+    // without growing children, space is left over at the end
+    assert_eq!(solve(&[Block(2), Percentage(50)], 10), vec![2, 5]);
+  }
+
+  #[test]
+  fn bounded_growth() {
+    assert_eq!(solve(&[Max(2), Grow(1)], 10), vec![2, 8]);
+    assert_eq!(solve(&[Min(7), Grow(1)], 10), vec![7, 3]);
+    assert_eq!(solve(&[Min(2), Grow(1)], 10), vec![5, 5]);
+    assert_eq!(solve(&[Bounded { grow: 3, min: 1, max: 4 }, Grow(1), Max(1)], 20), vec![4, 15, 1]);
+    assert_eq!(solve(&[Max(2), Max(3)], 10), vec![2, 3]);
+  }
+
+  #[test]
+  fn overflow_shrinks_last_children() {
+    assert_eq!(solve(&[Block(6), Block(6), Grow(1)], 10), vec![6, 4, 0]);
+    assert_eq!(solve(&[Min(4), Block(8)], 10), vec![4, 6]);
+    assert_eq!(solve(&[Block(3)], 0), vec![0]);
+  }
+
+  #[test]
+  fn gaps_stay_within_available() {
+    assert_eq!(place(&[Grow(1), Grow(1)], 2, 5, 1), vec![(2, 2), (5, 2)]);
+    assert_eq!(place(&[Grow(1); 5], 0, 3, 1), vec![(0, 0), (1, 0), (2, 0), (3, 0), (3, 0)]);
+    assert_eq!(place(&[Block(2), Block(2)], 0, 3, 2), vec![(0, 1), (3, 0)]);
+  }
+}
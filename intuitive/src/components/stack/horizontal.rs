@@ -1,18 +1,19 @@
-use tui::layout::{Constraint, Direction, Layout};
+use tui::widgets::{Block, Borders};
 
-use super::{Flex, FlexArray};
+use super::{flex, Flex, FlexList};
 use crate::{
   component,
-  components::children::Children,
+  components::children::List,
   element::{Any as AnyElement, Element},
-  event::{self, KeyEvent, KeyHandler, MouseEvent, MouseHandler},
-  terminal::{Frame, Rect},
+  event::{handler::Propagate, KeyEvent, KeyHandler, MouseEvent, MouseHandler},
+  style::Style,
+  terminal::{Frame, Rect, Size},
 };
 
 /// A component that for renders a horizontal stack of components.
 ///
 /// The `flex` argument specifies the amount of space allocated to each child, similar
-/// to the [`flex` css property]. See the [`FlexArray`] documentation for details.
+/// to the [`flex` css property]. See the [`Flex`] documentation for details.
 ///
 /// An example usage would be,
 /// ```rust
@@ -33,81 +34,127 @@ use crate::{
 ///
 /// ![hstack](https://raw.githubusercontent.com/enricozb/intuitive/main/assets/hstack.png)
 ///
+/// `gap` inserts empty columns between children. When a `separator` style is given, a
+/// vertical line in that style is drawn in the middle of each gap, which is then at least
+/// one column wide.
+///
+/// Events that are not handled by the children of the stack bubble up to its `on_key`
+/// and `on_mouse` handlers, while `on_key_capture` and `on_mouse_capture` receive events
+/// before its children do, as described in [`Element`].
+///
+/// [`Element`]: ../element/trait.Element.html
 /// [`flex` css property]: https://developer.mozilla.org/en-US/docs/Web/CSS/flex
-/// [`FlexArray`]: struct.FlexArray.html
-#[component(Stack<const N: usize>)]
-pub fn render(flex: FlexArray<N>, children: Children<N>, on_key: KeyHandler, on_mouse: MouseHandler) {
+/// [`Flex`]: stack/enum.Flex.html
+#[component(Stack)]
+pub fn render(
+  flex: FlexList,
+  gap: u16,
+  separator: Option<Style>,
+  children: List,
+  on_key: KeyHandler,
+  on_key_capture: KeyHandler,
+  on_mouse: MouseHandler,
+  on_mouse_capture: MouseHandler,
+) {
   AnyElement::new(Frozen {
-    flex: *flex,
+    flex: flex.for_children(children.len()),
+    gap: if separator.is_some() { (*gap).max(1) } else { *gap },
+    separator: *separator,
 
     children: children.render(),
     on_key: on_key.clone(),
+    on_key_capture: on_key_capture.clone(),
     on_mouse: on_mouse.clone(),
+    on_mouse_capture: on_mouse_capture.clone(),
   })
 }
 
-struct Frozen<const N: usize> {
-  flex: FlexArray<N>,
+struct Frozen {
+  flex: Vec<Flex>,
+  gap: u16,
+  separator: Option<Style>,
 
-  children: [AnyElement; N],
+  children: Vec<AnyElement>,
   on_key: KeyHandler,
+  on_key_capture: KeyHandler,
   on_mouse: MouseHandler,
+  on_mouse_capture: MouseHandler,
 }
 
-impl<const N: usize> Frozen<N> {
+impl Frozen {
   fn layout(&self, rect: Rect) -> Vec<Rect> {
-    let total_grow: u16 = self
-      .flex
-      .iter()
-      .map(|flex| match flex {
-        Flex::Grow(grow) => *grow,
-        Flex::Block(_) => 0,
-      })
-      .sum();
+    let flex = flex::measure_auto(&self.flex, |i| self.children[i].measure(rect.into()).width);
 
-    let total_px: u16 = self
-      .flex
-      .iter()
-      .map(|flex| match flex {
-        Flex::Block(px) => *px,
-        Flex::Grow(_) => 0,
-      })
-      .sum();
+    flex::place(&flex, rect.x, rect.width, self.gap)
+      .into_iter()
+      .map(|(x, width)| Rect { x, width, ..rect })
+      .collect()
+  }
+
+  fn draw_separators(&self, area: Rect, rects: &[Rect], style: Style, frame: &mut Frame) {
+    for (rect, next) in rects.iter().zip(rects.iter().skip(1)) {
+      let end = rect.x + rect.width;
+      if next.x <= end || end >= area.right() {
+        continue;
+      }
 
-    let grow_px = rect.width - total_px;
+      let line = Rect {
+        x: end + (next.x - end - 1) / 2,
+        width: 1,
+        ..*rect
+      };
 
-    Layout::default()
-      .direction(Direction::Horizontal)
-      .constraints(self.flex.map(|flex| match flex {
-        Flex::Block(px) => Constraint::Length(px),
-        Flex::Grow(grow) => Constraint::Length(grow * grow_px / total_grow),
-      }))
-      .split(rect)
+      frame.render_widget(Block::default().borders(Borders::LEFT).border_style(style.into()), line);
+    }
   }
 }
 
-impl<const N: usize> Element for Frozen<N> {
-  fn on_key(&self, event: KeyEvent) {
-    self.on_key.handle(event);
+impl Element for Frozen {
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
   }
 
-  fn on_mouse(&self, rect: Rect, event: MouseEvent) {
-    self.on_mouse.handle_or(event, |event| {
-      for (i, rect) in self.layout(rect).into_iter().enumerate() {
-        if event::is_within(&event, rect) {
-          self.children[i].on_mouse(rect, event);
+  fn on_key_capture(&self, event: KeyEvent) -> Propagate {
+    self.on_key_capture.handle(event)
+  }
 
-          break;
-        }
-      }
-    });
+  fn on_mouse(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+    self.on_mouse.handle(event)
+  }
+
+  fn on_mouse_capture(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+    self.on_mouse_capture.handle(event)
+  }
+
+  fn measure(&self, available: Size) -> Size {
+    let sizes: Vec<Size> = self.children.iter().map(|child| child.measure(available)).collect();
+
+    // the stack only has a size of its own if none of its children grow
+    let gaps = self.gap.saturating_mul(sizes.len().saturating_sub(1) as u16);
+    let width = self
+      .flex
+      .iter()
+      .zip(&sizes)
+      .map(|(flex, size)| match flex {
+        Flex::Block(block) => Some(*block),
+        Flex::Auto => Some(size.width),
+        _ => None,
+      })
+      .try_fold(gaps, |total, size| size.map(|size| total.saturating_add(size)))
+      .unwrap_or(available.width);
+    let height = sizes.iter().map(|size| size.height).max().unwrap_or_default();
+
+    Size { width, height }.min(available)
   }
 
   fn draw(&self, rect: Rect, frame: &mut Frame) {
-    let layout = self.layout(rect);
+    let rects = self.layout(rect);
+    if let Some(separator) = self.separator {
+      self.draw_separators(rect, &rects, separator, frame);
+    }
 
-    for (i, child) in self.children.iter().enumerate() {
-      child.draw(*layout.get(i).expect("missing rect"), frame);
+    for (child, rect) in self.children.iter().zip(rects) {
+      child.draw(rect, frame);
     }
   }
 }
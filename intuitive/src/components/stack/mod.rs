@@ -4,4 +4,130 @@ mod flex;
 pub(super) mod horizontal;
 pub(super) mod vertical;
 
-pub use self::flex::{Array as FlexArray, Flex};
+pub(crate) use self::flex::{measure_auto, place};
+pub use self::flex::{Array as FlexArray, Flex, List as FlexList};
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use super::Flex::*;
+  use crate::{
+    components::{Any as AnyComponent, Empty, HStack, Section, Text, VStack},
+    element::Any as AnyElement,
+    event::tree::{self, rect_at},
+    render,
+    style::Color,
+    terminal::Rect,
+  };
+
+  #[test]
+  #[serial]
+  fn spreads_children() {
+    let lines = vec!["b", "c", "d"];
+    let flex = [Block(1)].into_iter().chain(lines.iter().map(|_| Grow(1))).collect::<Vec<_>>();
+
+    let stack: AnyComponent = render! {
+      VStack(flex) {
+        Text(text: "a")
+        ..lines.into_iter().map(|line| render! { Text(text: line) })
+      }
+    };
+
+    let element: AnyElement = stack.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 10));
+
+    assert_eq!(rect_at(0, 0), Some(Rect::new(0, 0, 10, 1)));
+    assert_eq!(rect_at(0, 1), Some(Rect::new(0, 1, 10, 3)));
+    assert_eq!(rect_at(0, 9), Some(Rect::new(0, 7, 10, 3)));
+  }
+
+  #[test]
+  #[serial]
+  fn separates_children() {
+    let stack: AnyComponent = render! {
+      VStack(flex: [1, 1, 1], gap: 2) {
+        Text(text: "a")
+        Text(text: "b")
+        Text(text: "c")
+      }
+    };
+
+    let element: AnyElement = stack.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 10));
+
+    assert_eq!(rect_at(0, 0), Some(Rect::new(0, 0, 10, 2)));
+    assert_eq!(rect_at(0, 4), Some(Rect::new(0, 4, 10, 2)));
+    assert_eq!(rect_at(0, 8), Some(Rect::new(0, 8, 10, 2)));
+
+    let stack: AnyComponent = render! {
+      VStack(separator: Color::Gray) {
+        Text(text: "a")
+        Text(text: "b")
+      }
+    };
+
+    let element: AnyElement = stack.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 9));
+
+    assert_eq!(rect_at(0, 0), Some(Rect::new(0, 0, 10, 4)));
+    assert_eq!(rect_at(0, 4), Some(Rect::new(0, 0, 10, 9)));
+    assert_eq!(rect_at(0, 5), Some(Rect::new(0, 5, 10, 4)));
+  }
+
+  #[test]
+  #[serial]
+  fn separates_children_without_room() {
+    let stack: AnyComponent = render! {
+      HStack(separator: Color::Gray) {
+        Text(text: "a")
+        Text(text: "b")
+        Text(text: "c")
+        Text(text: "d")
+        Text(text: "e")
+      }
+    };
+
+    // the separators alone do not fit, so the children have no width
+    let element: AnyElement = stack.render();
+    tree::draw(&element, Rect::new(0, 0, 3, 1));
+
+    assert_eq!(rect_at(0, 0), Some(Rect::new(0, 0, 3, 1)));
+  }
+
+  #[test]
+  #[serial]
+  fn sizes_children_to_content() {
+    let stack: AnyComponent = render! {
+      VStack(flex: [Auto, Auto, Grow(1)]) {
+        Text(text: "a\nb")
+        Section(title: "section") {
+          Text(text: "c")
+        }
+        Text(text: "d")
+      }
+    };
+
+    let element: AnyElement = stack.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 10));
+
+    assert_eq!(rect_at(0, 1), Some(Rect::new(0, 0, 10, 2)));
+    assert_eq!(rect_at(0, 2), Some(Rect::new(0, 2, 10, 3)));
+    assert_eq!(rect_at(0, 5), Some(Rect::new(0, 5, 10, 5)));
+
+    let stack: AnyComponent = render! {
+      VStack(flex: [Auto, Grow(1)]) {
+        Empty()
+        Section(title: "section") {
+          Text(text: "a")
+        }
+      }
+    };
+
+    // an empty child takes no room, leaving all of it to the section
+    let element: AnyElement = stack.render();
+    tree::draw(&element, Rect::new(0, 0, 10, 10));
+
+    assert_eq!(rect_at(1, 1), Some(Rect::new(1, 1, 8, 8)));
+  }
+}
@@ -3,22 +3,26 @@ use tui::{text::Spans as TuiSpans, widgets::Paragraph};
 use crate::{
   component,
   element::{Any as AnyElement, Element},
-  event::{KeyEvent, KeyHandler, MouseEvent, MouseHandler},
-  terminal::{Frame, Rect},
+  event::{self, handler::Propagate, Gesture, GestureHandler, KeyEvent, KeyHandler, MouseEvent, MouseHandler},
+  terminal::{Frame, Rect, Size},
   text::Lines,
 };
 
 /// A component that displays text.
 ///
-/// `Text` renders the [`Lines`] passed into it.
+/// `Text` renders the [`Lines`] passed into it. Mouse events within the text are sent to
+/// `on_mouse`, clicks on it to `on_click`, and all of its [`Gesture`]s to `on_gesture`.
 ///
+/// [`Gesture`]: ../event/enum.Gesture.html
 /// [`Lines`]: ../text/struct.Lines.html
 #[component(Text)]
-pub fn render(text: Lines, on_key: KeyHandler, on_mouse: MouseHandler) {
+pub fn render(text: Lines, on_key: KeyHandler, on_mouse: MouseHandler, on_click: MouseHandler, on_gesture: GestureHandler) {
   AnyElement::new(Frozen {
     lines: text.clone(),
     on_key: on_key.clone(),
     on_mouse: on_mouse.clone(),
+    on_click: on_click.clone(),
+    on_gesture: on_gesture.clone(),
   })
 }
 
@@ -26,15 +30,39 @@ struct Frozen {
   lines: Lines,
   on_key: KeyHandler,
   on_mouse: MouseHandler,
+  on_click: MouseHandler,
+  on_gesture: GestureHandler,
 }
 
 impl Element for Frozen {
-  fn on_key(&self, event: KeyEvent) {
-    self.on_key.handle(event);
+  fn on_key(&self, event: KeyEvent) -> Propagate {
+    self.on_key.handle(event)
   }
 
-  fn on_mouse(&self, _rect: Rect, event: MouseEvent) {
-    self.on_mouse.handle(event);
+  fn on_mouse(&self, rect: Rect, event: MouseEvent) -> Propagate {
+    if !event::is_within(&event, rect) {
+      return Propagate::Next;
+    }
+
+    self.on_mouse.handle(event)
+  }
+
+  fn on_gesture(&self, _rect: Rect, gesture: Gesture) -> Propagate {
+    self.on_gesture.handle_or(gesture, |gesture| match gesture {
+      Gesture::Click(event) => self.on_click.handle(event),
+      _ => Propagate::Next,
+    })
+  }
+
+  fn measure(&self, available: Size) -> Size {
+    let width = self.lines.0.iter().map(|spans| TuiSpans::from(spans).width()).max();
+    let height = self.lines.0.len();
+
+    Size {
+      width: u16::try_from(width.unwrap_or_default()).unwrap_or(u16::MAX),
+      height: u16::try_from(height).unwrap_or(u16::MAX),
+    }
+    .min(available)
   }
 
   fn draw(&self, rect: Rect, frame: &mut Frame) {
@@ -4,8 +4,8 @@ use std::{ops::Deref, sync::Arc};
 
 use crate::{
   components::{Component, Empty},
-  event::{KeyEvent, MouseEvent},
-  terminal::{Frame, Rect},
+  event::{handler::Propagate, tree, Gesture, KeyEvent, MouseEvent},
+  terminal::{Frame, Rect, Size},
 };
 
 /// An opaque type holding a struct that implements [`Element`].
@@ -18,6 +18,16 @@ impl Any {
   pub fn new<C: Element + 'static + Send + Sync>(element: C) -> Self {
     Self(Arc::new(element))
   }
+
+  /// Draws the element, recording the [`Rect`] it was drawn into so that events can be
+  /// dispatched to it.
+  ///
+  /// [`Rect`]: https://docs.rs/tui/latest/tui/layout/struct.Rect.html
+  pub fn draw(&self, rect: Rect, frame: &mut Frame) {
+    tree::enter(rect, self.clone());
+    self.0.draw(rect, frame);
+    tree::exit();
+  }
 }
 
 impl Default for Any {
@@ -49,22 +59,97 @@ impl<C: Component + 'static> From<C> for Any {
 /// Intuitive internally uses [tui] in order to draw to the terminal. The [`Rect`]
 /// and [`Frame`] structures are re-exports from [tui].
 ///
-/// # Handling Keys
+/// # Measuring
+/// Elements may report the size they would like to be drawn at through [`measure`],
+/// which is used to size children with a [`Flex::Auto`] in stacks and grids, or with
+/// a [`Length::Auto`] in an [`Align`]. The default implementation takes up all of the
+/// available space. Elements with a single child that is drawn into the same [`Rect`]
+/// should return the size of the child, while elements drawing text or borders, such
+/// as [`Text`] and [`Section`], return the size of their contents.
+///
+/// # Handling Events
 /// Typically, structures that implement `Element` do not have any [`State`].
 /// Usually, an `Element` will contain an `on_key` field which has captured any
 /// state that could be mutated, and then the `Element` will delegate key events
 /// to its `on_key` field. See the [`Section` source] for an example of this.
 ///
+/// Every [`Any`] records the [`Rect`] it was drawn into, and events are dispatched
+/// along the path of drawn elements from the root to a target element:
+/// - key events and pasted text target the focused element, as described in the
+///   [`focus`] module, or the element shown by a [`Modal`]. Otherwise, they target the
+///   deepest element reached from the root through elements with a single child.
+/// - mouse events target the deepest element under the cursor.
+///
+/// An event is first sent through the capture phase, calling [`on_key_capture`],
+/// [`on_mouse_capture`], or [`on_paste_capture`] on each element from the root down to
+/// the target. It is then sent through the bubble phase, calling [`on_key`],
+/// [`on_mouse`], or [`on_paste`] on each element from the target back up to the root.
+/// These return a [`Propagate`], and dispatching stops at the first element returning
+/// [`Propagate`]`::Stop`. Since events reach children this way, elements should not
+/// forward events to their children themselves.
+///
+/// Clicks, double-clicks, drags, and the cursor entering or leaving an element are
+/// sent as [`Gesture`]s through [`on_gesture`], bubbling up from the element they
+/// happened on.
+///
+/// [`Align`]: ../components/struct.Align.html
+/// [`Any`]: struct.Any.html
 /// [`Component`]: ../components/trait.Component.html
 /// [`draw`]: #method.draw
+/// [`focus`]: ../focus/index.html
 /// [`Frame`]: https://docs.rs/tui/latest/tui/terminal/struct.Frame.html
+/// [`Flex::Auto`]: ../components/stack/enum.Flex.html#variant.Auto
+/// [`Gesture`]: ../event/enum.Gesture.html
+/// [`Length::Auto`]: ../components/align/enum.Length.html#variant.Auto
+/// [`measure`]: #method.measure
+/// [`Modal`]: ../components/experimental/modal/struct.Modal.html
+/// [`on_gesture`]: #method.on_gesture
 /// [`on_key`]: #method.on_key
+/// [`on_key_capture`]: #method.on_key_capture
+/// [`on_mouse`]: #method.on_mouse
+/// [`on_mouse_capture`]: #method.on_mouse_capture
+/// [`on_paste`]: #method.on_paste
+/// [`on_paste_capture`]: #method.on_paste_capture
+/// [`Propagate`]: ../event/handler/enum.Propagate.html
 /// [`Rect`]: https://docs.rs/tui/latest/tui/layout/struct.Rect.html
+/// [`Section`]: ../components/struct.Section.html
 /// [`Section` source]: ../../src/intuitive/components/section.rs.html
 /// [`State`]: ../state/struct.State.html
+/// [`Text`]: ../components/struct.Text.html
 /// [tui]: https://docs.rs/tui/latest/tui/
 pub trait Element {
   fn draw(&self, _rect: Rect, _frame: &mut Frame) {}
-  fn on_key(&self, _event: KeyEvent) {}
-  fn on_mouse(&self, _rect: Rect, _event: MouseEvent) {}
+
+  /// Returns the size the element would like to be drawn at, within `available`.
+  fn measure(&self, available: Size) -> Size {
+    available
+  }
+
+  fn on_key(&self, _event: KeyEvent) -> Propagate {
+    Propagate::Next
+  }
+
+  fn on_key_capture(&self, _event: KeyEvent) -> Propagate {
+    Propagate::Next
+  }
+
+  fn on_mouse(&self, _rect: Rect, _event: MouseEvent) -> Propagate {
+    Propagate::Next
+  }
+
+  fn on_mouse_capture(&self, _rect: Rect, _event: MouseEvent) -> Propagate {
+    Propagate::Next
+  }
+
+  fn on_paste(&self, _text: &str) -> Propagate {
+    Propagate::Next
+  }
+
+  fn on_paste_capture(&self, _text: &str) -> Propagate {
+    Propagate::Next
+  }
+
+  fn on_gesture(&self, _rect: Rect, _gesture: Gesture) -> Propagate {
+    Propagate::Next
+  }
 }
@@ -22,4 +22,13 @@ pub enum Error {
 
   #[error("use_state calls must be in the same order: {0}")]
   UseState(String),
+
+  #[error("hooks must be called in the same order in every render: {0}")]
+  HookOrder(String),
+
+  #[error("invalid key binding `{0}`: {1}")]
+  InvalidKey(String, String),
+
+  #[error("keymap: {0}")]
+  Keymap(String),
 }
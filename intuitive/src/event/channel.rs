@@ -21,7 +21,7 @@ pub(crate) fn read() -> Result<Event> {
   CHANNEL.recv()
 }
 
-fn send(event: Event) -> Result<()> {
+pub(crate) fn send(event: Event) -> Result<()> {
   CHANNEL.send(event)
 }
 
@@ -51,6 +51,7 @@ pub fn start_crossterm_events() {
     let event = match crossterm_event::read().expect("read") {
       CrosstermEvent::Key(event) => Event::Key(event),
       CrosstermEvent::Mouse(event) => Event::Mouse(event),
+      CrosstermEvent::Paste(text) => Event::Paste(text),
       CrosstermEvent::Resize(..) => Event::Render,
 
       _ => continue,
@@ -0,0 +1,164 @@
+use std::{
+  any::{Any, TypeId},
+  mem,
+  sync::Arc,
+};
+
+use parking_lot::Mutex;
+
+use super::{channel, Event};
+use crate::{error::Result, state};
+
+static LISTENERS: Mutex<Listeners> = Mutex::new(Listeners::new());
+
+type Listener = Arc<dyn Fn(&dyn Any) + 'static + Send + Sync>;
+
+/// The listeners registered through [`use_event`], by the type of event they listen to.
+///
+/// [`use_event`]: fn.use_event.html
+struct Listeners {
+  listeners: Vec<(TypeId, Listener)>,
+  rendering: Vec<(TypeId, Listener)>,
+}
+
+impl Listeners {
+  const fn new() -> Self {
+    Self {
+      listeners: Vec::new(),
+      rendering: Vec::new(),
+    }
+  }
+}
+
+/// Sends an application-defined event to the listeners registered through
+/// [`use_event`].
+///
+/// Events are sent through the same channel as key and mouse events, so they are handled
+/// in the order in which they were sent, after a re-render. This can be called from any
+/// thread, which is useful for reporting progress from background work:
+/// ```rust
+/// # use std::thread;
+/// # use intuitive::{component, components::Text, event, render, state::use_state};
+/// #
+/// struct JobDone(usize);
+///
+/// #[component(Jobs)]
+/// fn render() {
+///   let done = use_state(Vec::new);
+///
+///   event::use_event({
+///     let done = done.clone();
+///     move |JobDone(id): &JobDone| done.mutate(|done| done.push(*id))
+///   });
+///
+///   render! {
+///     Text(text: format!("finished jobs: {:?}", done.get()))
+///   }
+/// }
+///
+/// thread::spawn(|| {
+///   for id in 0..3 {
+///     /* do some work */
+///     event::emit(JobDone(id)).unwrap();
+///   }
+/// });
+/// ```
+///
+/// # Errors
+/// Returns an error if the event could not be sent.
+///
+/// [`use_event`]: fn.use_event.html
+pub fn emit<T: Any + Send>(event: T) -> Result<()> {
+  channel::send(Event::Custom(Box::new(event)))
+}
+
+/// Calls `handler` on every event of type `T` sent through [`emit`].
+///
+/// Like [`use_modal`], this may only be called within a call to [`Component::render`].
+/// Listeners are registered anew on every render, so a component only receives events
+/// while it is rendered, and can listen to events conditionally.
+///
+/// [`Component::render`]: ../components/trait.Component.html#tymethod.render
+/// [`emit`]: fn.emit.html
+/// [`use_modal`]: ../components/experimental/modal/fn.use_modal.html
+pub fn use_event<T, F>(handler: F)
+where
+  T: Any,
+  F: Fn(&T) + 'static + Send + Sync,
+{
+  let listener: Listener = Arc::new(move |event: &dyn Any| {
+    if let Some(event) = event.downcast_ref::<T>() {
+      handler(event);
+    }
+  });
+
+  state::effect(move || LISTENERS.lock().rendering.push((TypeId::of::<T>(), listener.clone())));
+}
+
+/// Marks the end of a render, replacing the previously registered listeners.
+pub(crate) fn render_done() {
+  let mut listeners = LISTENERS.lock();
+
+  listeners.listeners = mem::take(&mut listeners.rendering);
+}
+
+/// Sends an event to the listeners of its type.
+pub(crate) fn dispatch(event: Box<dyn Any + Send>) {
+  let type_id = (*event).type_id();
+
+  let listeners: Vec<Listener> = LISTENERS
+    .lock()
+    .listeners
+    .iter()
+    .filter(|(listener_type, _)| *listener_type == type_id)
+    .map(|(_, listener)| listener.clone())
+    .collect();
+
+  // listeners may re-render, so they must be called without the lock
+  for listener in listeners {
+    listener(&*event);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use super::*;
+  use crate::state::State;
+
+  struct Log(&'static str);
+
+  #[test]
+  #[serial]
+  fn events_reach_listeners_of_their_type() {
+    *LISTENERS.lock() = Listeners::new();
+
+    let received = State::new(Vec::new());
+
+    for name in ["first", "second"] {
+      let received = received.clone();
+      use_event(move |Log(line): &Log| received.mutate(|received| received.push(format!("{} {}", name, line))));
+    }
+
+    let counted = State::new(0);
+    use_event({
+      let counted = counted.clone();
+      move |count: &usize| counted.set(*count)
+    });
+
+    render_done();
+
+    dispatch(Box::new(Log("started")));
+    dispatch(Box::new(3usize));
+    dispatch(Box::new(3u32));
+
+    assert_eq!(received.get(), vec!["first started", "second started"]);
+    assert_eq!(counted.get(), 3);
+
+    // listeners that are no longer rendered do not receive events
+    render_done();
+    dispatch(Box::new(Log("stopped")));
+    assert_eq!(received.get().len(), 2);
+  }
+}
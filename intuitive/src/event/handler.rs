@@ -1,6 +1,12 @@
 use std::sync::Arc;
 
+use crate::event;
+
 /// Whether to propagate the event to the next handler.
+///
+/// `()` converts into `Propagate::Stop`, so that handlers which do not explicitly
+/// propagate an event stop its propagation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Propagate {
   /// Continues handler propagation.
   Next,
@@ -8,15 +14,47 @@ pub enum Propagate {
   Stop,
 }
 
+impl From<()> for Propagate {
+  fn from((): ()) -> Self {
+    Self::Stop
+  }
+}
+
+/// A description of what a handler does for some keys, used to display help.
+///
+/// [`Hint`]s are attached to a handler through [`Handler::hint`], and are displayed by
+/// the experimental [`Help`] and [`HintLine`] components.
+///
+/// [`Handler::hint`]: struct.Handler.html#method.hint
+/// [`Help`]: ../../components/experimental/help/struct.Help.html
+/// [`Hint`]: struct.Hint.html
+/// [`HintLine`]: ../../components/experimental/help/struct.HintLine.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hint {
+  pub keys: String,
+  pub description: String,
+}
+
+impl Hint {
+  pub fn new<K: Into<String>, D: Into<String>>(keys: K, description: D) -> Self {
+    Self {
+      keys: keys.into(),
+      description: description.into(),
+    }
+  }
+}
+
 /// A generic handler for mouse and keyboard events.
 pub struct Handler<T> {
   handler: Arc<dyn Fn(T) -> Propagate + 'static + Send + Sync>,
+  hints: Arc<Vec<Hint>>,
 }
 
 impl<T> Default for Handler<T> {
   fn default() -> Self {
     Self {
       handler: Arc::new(|_| Propagate::Next),
+      hints: Arc::default(),
     }
   }
 }
@@ -25,40 +63,78 @@ impl<T> Clone for Handler<T> {
   fn clone(&self) -> Self {
     Self {
       handler: self.handler.clone(),
+      hints: self.hints.clone(),
     }
   }
 }
 
-impl<T: 'static + Copy> Handler<T> {
+/// `Handler`s are equal if they were cloned from one another.
+impl<T> PartialEq for Handler<T> {
+  fn eq(&self, other: &Self) -> bool {
+    Arc::ptr_eq(&self.handler, &other.handler) && Arc::ptr_eq(&self.hints, &other.hints)
+  }
+}
+
+impl<T> Handler<T> {
+  /// Describes what the handler does when `keys` are pressed.
+  pub fn hint(mut self, keys: &str, description: &str) -> Self {
+    Arc::make_mut(&mut self.hints).push(Hint::new(keys, description));
+    self
+  }
+
+  /// Returns the descriptions of what the handler does.
+  pub fn hints(&self) -> &[Hint] {
+    &self.hints
+  }
+}
+
+impl<T: 'static + Clone> Handler<T> {
   /// Call the handler on the event.
-  pub fn handle(&self, event: T) {
-    self.handle_or(event, |_| {});
+  pub fn handle(&self, event: T) -> Propagate {
+    (self.handler)(event)
   }
 
   /// Call the handler on the event, defaulting to the alternative_handler.
-  pub fn handle_or<F, R>(&self, event: T, alternative_handler: F)
+  ///
+  /// Returns whatever the alternative handler returns if it was called. The alternative
+  /// handler is not called if the default action of the event was prevented through
+  /// [`event::prevent_default`].
+  ///
+  /// [`event::prevent_default`]: ../fn.prevent_default.html
+  pub fn handle_or<F>(&self, event: T, alternative_handler: F) -> Propagate
   where
-    F: FnOnce(T) -> R,
+    F: FnOnce(T) -> Propagate,
   {
-    match (self.handler)(event) {
-      Propagate::Next => drop(alternative_handler(event)),
-      Propagate::Stop => (),
+    match (self.handler)(event.clone()) {
+      Propagate::Next if event::is_default_prevented() => Propagate::Next,
+      Propagate::Next => alternative_handler(event),
+      Propagate::Stop => Propagate::Stop,
     }
   }
 
   /// Create a new handler that propagates to `next_handler`.
   ///
-  /// Propagation only occurs if this handler returns `Propagate::Next`.
+  /// Propagation only occurs if this handler returns `Propagate::Next`, and the default
+  /// action of the event was not prevented through [`event::prevent_default`], so
+  /// `next_handler` is typically a default action. The new handler keeps the [`Hint`]s of
+  /// this one.
+  ///
+  /// [`event::prevent_default`]: ../fn.prevent_default.html
+  /// [`Hint`]: struct.Hint.html
   pub fn then<F>(&self, next_handler: F) -> Self
   where
     F: Fn(T) -> Propagate + 'static + Send + Sync,
   {
     let handler = self.handler.clone();
 
-    Handler::from(move |event| match handler(event) {
-      Propagate::Next => next_handler(event),
-      Propagate::Stop => Propagate::Stop,
-    })
+    Self {
+      handler: Arc::new(move |event: T| match handler(event.clone()) {
+        Propagate::Next if event::is_default_prevented() => Propagate::Next,
+        Propagate::Next => next_handler(event),
+        Propagate::Stop => Propagate::Stop,
+      }),
+      hints: self.hints.clone(),
+    }
   }
 }
 
@@ -67,7 +143,10 @@ where
   F: Fn(T) -> Propagate + 'static + Send + Sync,
 {
   fn from(f: F) -> Self {
-    Self { handler: Arc::new(f) }
+    Self {
+      handler: Arc::new(f),
+      hints: Arc::default(),
+    }
   }
 }
 
@@ -0,0 +1,413 @@
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  fmt,
+  sync::Arc,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::{Action, Keymap, Sequence};
+use crate::{
+  error::{Error, Result},
+  event::handler::Propagate,
+};
+
+/// The key sequences bound to each action in a section of a [`Config`].
+///
+/// [`Config`]: struct.Config.html
+type Section = BTreeMap<String, Vec<String>>;
+
+/// Named actions that can be bound to keys through a [`Config`].
+///
+/// [`Config`]: struct.Config.html
+#[derive(Clone, Default)]
+pub struct Actions {
+  actions: BTreeMap<String, (String, Action)>,
+}
+
+impl Actions {
+  /// Creates an empty set of actions.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Declares the action `name`, with a description of what it does.
+  ///
+  /// The action may return `()`, or a [`Propagate`] to let the last key of the sequence
+  /// propagate. Declaring an action that already exists replaces it.
+  ///
+  /// [`Propagate`]: ../handler/enum.Propagate.html
+  pub fn add<F, R>(mut self, name: &str, description: &str, action: F) -> Self
+  where
+    F: Fn() -> R + 'static + Send + Sync,
+    R: Into<Propagate>,
+  {
+    self
+      .actions
+      .insert(name.to_string(), (description.to_string(), Arc::new(move || action().into())));
+
+    self
+  }
+
+  /// Returns the description of the action `name`, if it exists.
+  pub fn description(&self, name: &str) -> Option<&str> {
+    self.actions.get(name).map(|(description, _)| description.as_str())
+  }
+}
+
+/// The keys bound to named [`Actions`], per mode.
+///
+/// A `Config` is usually made of an application's default bindings, merged with those
+/// of a user's config file. With the `toml` feature, a config file looks like:
+/// ```toml
+/// # bound in every mode
+/// [global]
+/// quit = "ctrl+c"
+///
+/// [modes.normal]
+/// insert = "i"
+/// top = ["g g", "home"]
+///
+/// [modes.insert]
+/// normal = "esc"
+/// ```
+/// where each action is bound to either a single key sequence or a list of them. An
+/// action bound to an empty list is unbound.
+///
+/// [`Actions`]: struct.Actions.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Config {
+  /// The bindings that are active in every mode.
+  #[cfg_attr(feature = "serde", serde(default, deserialize_with = "de::section"))]
+  pub global: Section,
+
+  /// The bindings of each mode.
+  #[cfg_attr(feature = "serde", serde(default, deserialize_with = "de::modes"))]
+  pub modes: BTreeMap<String, Section>,
+}
+
+impl Config {
+  /// Creates an empty `Config`.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Binds `action` to `keys` in every mode.
+  pub fn bind(mut self, action: &str, keys: &str) -> Self {
+    self.global.entry(action.to_string()).or_default().push(keys.to_string());
+    self
+  }
+
+  /// Binds `action` to `keys` in `mode`.
+  pub fn bind_in(mut self, mode: &str, action: &str, keys: &str) -> Self {
+    self
+      .modes
+      .entry(mode.to_string())
+      .or_default()
+      .entry(action.to_string())
+      .or_default()
+      .push(keys.to_string());
+
+    self
+  }
+
+  /// Overrides the bindings of `self` with those of `other`.
+  ///
+  /// Every action that `other` binds in a section, that is, globally or in a mode,
+  /// replaces the bindings of that action in the same section of `self`. All other
+  /// bindings of `self` are kept.
+  pub fn merge(mut self, other: Config) -> Self {
+    self.global.extend(other.global);
+
+    for (mode, bindings) in other.modes {
+      self.modes.entry(mode).or_default().extend(bindings);
+    }
+
+    self
+  }
+
+  /// Returns every problem with the config: actions missing from `actions`, invalid key
+  /// sequences, and key sequences bound to more than one action in the same section.
+  pub fn problems(&self, actions: &Actions) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    for (mode, bindings) in self.sections() {
+      // sequences are keyed by their canonical form, so that equivalent spellings conflict
+      let mut bound: BTreeMap<String, (Sequence, BTreeSet<&str>)> = BTreeMap::new();
+
+      for (action, keys) in bindings {
+        if !actions.actions.contains_key(action) {
+          problems.push(Problem::UnknownAction {
+            mode: mode.map(String::from),
+            action: action.clone(),
+          });
+        }
+
+        for keys in keys {
+          match keys.parse::<Sequence>() {
+            Ok(sequence) => {
+              bound
+                .entry(sequence.to_string())
+                .or_insert((sequence, BTreeSet::new()))
+                .1
+                .insert(action);
+            }
+
+            Err(err) => problems.push(Problem::InvalidKeys {
+              mode: mode.map(String::from),
+              action: action.clone(),
+              error: err.to_string(),
+            }),
+          }
+        }
+      }
+
+      for (keys, bound) in bound.into_values().filter(|(_, bound)| bound.len() > 1) {
+        problems.push(Problem::Conflict {
+          mode: mode.map(String::from),
+          keys,
+          actions: bound.into_iter().map(String::from).collect(),
+        });
+      }
+    }
+
+    problems
+  }
+
+  /// The global bindings, followed by the bindings of each mode.
+  fn sections(&self) -> impl Iterator<Item = (Option<&str>, &Section)> {
+    let modes = self.modes.iter().map(|(mode, bindings)| (Some(mode.as_str()), bindings));
+
+    std::iter::once((None, &self.global)).chain(modes)
+  }
+
+  /// Parses a `Config` from TOML.
+  #[doc_cfg::doc_cfg(feature = "toml")]
+  #[cfg(feature = "toml")]
+  pub fn from_toml(s: &str) -> Result<Self> {
+    toml::from_str(s).map_err(|err| Error::Keymap(err.to_string()))
+  }
+
+  /// Serializes the `Config` to TOML.
+  #[doc_cfg::doc_cfg(feature = "toml")]
+  #[cfg(feature = "toml")]
+  pub fn to_toml(&self) -> Result<String> {
+    toml::to_string(self).map_err(|err| Error::Keymap(err.to_string()))
+  }
+
+  /// Parses a `Config` from JSON.
+  #[doc_cfg::doc_cfg(feature = "json")]
+  #[cfg(feature = "json")]
+  pub fn from_json(s: &str) -> Result<Self> {
+    serde_json::from_str(s).map_err(|err| Error::Keymap(err.to_string()))
+  }
+
+  /// Serializes the `Config` to JSON.
+  #[doc_cfg::doc_cfg(feature = "json")]
+  #[cfg(feature = "json")]
+  pub fn to_json(&self) -> Result<String> {
+    serde_json::to_string_pretty(self).map_err(|err| Error::Keymap(err.to_string()))
+  }
+}
+
+/// A problem with a [`Config`], as returned by [`Config::problems`].
+///
+/// [`Config`]: struct.Config.html
+/// [`Config::problems`]: struct.Config.html#method.problems
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+  /// The action was not declared in the [`Actions`].
+  ///
+  /// [`Actions`]: struct.Actions.html
+  UnknownAction { mode: Option<String>, action: String },
+
+  /// A key sequence bound to the action could not be parsed.
+  InvalidKeys {
+    mode: Option<String>,
+    action: String,
+    error: String,
+  },
+
+  /// The key sequence is bound to more than one action.
+  Conflict {
+    mode: Option<String>,
+    keys: Sequence,
+    actions: Vec<String>,
+  },
+}
+
+struct Header<'a>(&'a Option<String>);
+
+impl fmt::Display for Header<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.0 {
+      Some(mode) => write!(f, "[modes.{}]", mode),
+      None => write!(f, "[global]"),
+    }
+  }
+}
+
+impl fmt::Display for Problem {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnknownAction { mode, action } => write!(f, "{}: unknown action `{}`", Header(mode), action),
+      Self::InvalidKeys { mode, action, error } => write!(f, "{}: `{}`: {}", Header(mode), action, error),
+      Self::Conflict { mode, keys, actions } => write!(
+        f,
+        "{}: `{}` is bound to more than one action: {}",
+        Header(mode),
+        keys,
+        actions.iter().map(|action| format!("`{}`", action)).collect::<Vec<_>>().join(", ")
+      ),
+    }
+  }
+}
+
+impl Keymap {
+  /// Creates a `Keymap` binding the `actions` to the keys in `config`, described by the
+  /// descriptions of the `actions`.
+  ///
+  /// # Errors
+  /// Returns an [`Error::Keymap`] describing every one of the [`Config::problems`], one
+  /// per line, if there are any.
+  ///
+  /// [`Config::problems`]: struct.Config.html#method.problems
+  /// [`Error::Keymap`]: ../../error/enum.Error.html#variant.Keymap
+  pub fn from_config(actions: &Actions, config: &Config) -> Result<Self> {
+    let problems = config.problems(actions);
+    if !problems.is_empty() {
+      let problems: Vec<String> = problems.iter().map(|problem| format!("  {}", problem)).collect();
+
+      return Err(Error::Keymap(format!(
+        "{} problem(s) with key bindings:\n{}",
+        problems.len(),
+        problems.join("\n")
+      )));
+    }
+
+    let mut keymap = Self::new();
+
+    for (mode, bindings) in config.sections() {
+      for (name, keys) in bindings {
+        let (description, action) = &actions.actions[name];
+
+        for keys in keys {
+          let action = action.clone();
+          let sequence = keys.parse().expect("validated sequence");
+
+          keymap = keymap.bind_sequence(mode, sequence, move || action()).describe(description);
+        }
+      }
+    }
+
+    Ok(keymap)
+  }
+}
+
+#[cfg(feature = "serde")]
+mod de {
+  use super::*;
+
+  /// Either a single key sequence or a list of them.
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum Keys {
+    One(String),
+    Many(Vec<String>),
+  }
+
+  impl From<Keys> for Vec<String> {
+    fn from(keys: Keys) -> Self {
+      match keys {
+        Keys::One(keys) => vec![keys],
+        Keys::Many(keys) => keys,
+      }
+    }
+  }
+
+  fn into_section(section: BTreeMap<String, Keys>) -> Section {
+    section.into_iter().map(|(action, keys)| (action, keys.into())).collect()
+  }
+
+  pub fn section<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Section, D::Error> {
+    BTreeMap::deserialize(deserializer).map(into_section)
+  }
+
+  pub fn modes<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<BTreeMap<String, Section>, D::Error> {
+    let modes: BTreeMap<String, BTreeMap<String, Keys>> = BTreeMap::deserialize(deserializer)?;
+
+    Ok(modes.into_iter().map(|(mode, section)| (mode, into_section(section))).collect())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn actions() -> Actions {
+    Actions::new()
+      .add("quit", "Quit", || ())
+      .add("save", "Save the file", || ())
+      .add("top", "Go to the top", || ())
+  }
+
+  fn defaults() -> Config {
+    Config::new()
+      .bind("quit", "ctrl+c")
+      .bind("save", "ctrl+s")
+      .bind_in("normal", "top", "g g")
+  }
+
+  #[test]
+  fn merge_overrides_actions() {
+    let user = Config::new().bind("save", "ctrl+x ctrl+s").bind_in("normal", "top", "home");
+    let config = defaults().merge(user);
+
+    assert_eq!(config.global["quit"], vec!["ctrl+c"]);
+    assert_eq!(config.global["save"], vec!["ctrl+x ctrl+s"]);
+    assert_eq!(config.modes["normal"]["top"], vec!["home"]);
+    assert!(config.problems(&actions()).is_empty());
+  }
+
+  #[test]
+  fn reports_problems() {
+    let user = Config::new()
+      .bind("save", "ctrl+c")
+      .bind("open", "ctrl+o")
+      .bind_in("normal", "top", "g gg");
+    let config = defaults().merge(user);
+
+    let problems: Vec<String> = config.problems(&actions()).iter().map(ToString::to_string).collect();
+    assert_eq!(
+      problems,
+      vec![
+        "[global]: unknown action `open`",
+        "[global]: `ctrl+c` is bound to more than one action: `quit`, `save`",
+        "[modes.normal]: `top`: invalid key binding `gg`: unknown key `gg`",
+      ]
+    );
+
+    let err = Keymap::from_config(&actions(), &config).err().expect("invalid config");
+    assert!(err.to_string().contains("3 problem(s)"));
+  }
+
+  #[cfg(feature = "toml")]
+  #[test]
+  fn toml_round_trips() {
+    let config = Config::from_toml(
+      r#"
+        [global]
+        quit = "ctrl+c"
+
+        [modes.normal]
+        top = ["g g", "home"]
+      "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.modes["normal"]["top"], vec!["g g", "home"]);
+    assert_eq!(Config::from_toml(&config.to_toml().unwrap()).unwrap(), config);
+  }
+}
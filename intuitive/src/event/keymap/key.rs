@@ -0,0 +1,274 @@
+use std::{fmt, str::FromStr};
+
+use intuitive_keys::{Code, Modifier};
+
+use crate::{
+  error::{Error, Result},
+  event::{KeyCode, KeyEvent, KeyModifiers},
+};
+
+/// A single key press, along with its modifiers.
+///
+/// A `Key` is parsed from strings like `"g"`, `"G"`, `"ctrl+x"`, `"alt+shift+left"`, or
+/// `"f5"`. Modifiers are `ctrl`, `alt`, `shift`, and `super`, and are separated from the
+/// key name by a `+`. The key names are:
+/// - any single character, such as `a`, `G`, `?`, or `0`
+/// - `space`, `plus`, and `minus`, for the characters `' '`, `'+'`, and `'-'`
+/// - `enter`, `esc`, `tab`, `backtab`, `backspace`, `delete`, `insert`
+/// - `up`, `down`, `left`, `right`, `home`, `end`, `pageup`, `pagedown`
+/// - `f1` through `f12`
+///
+/// Since the terminal already reports the character of a shifted key, `shift` is dropped
+/// from character keys, so `"shift+g"` and `"G"` are the same `Key`. Similarly,
+/// `"shift+tab"` is the same as `"backtab"`. Modifiers and named keys are
+/// case-insensitive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+  code: KeyCode,
+  modifiers: KeyModifiers,
+}
+
+impl Key {
+  /// Creates a new `Key`.
+  pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+    let mut modifiers = modifiers;
+
+    let code = match code {
+      KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+        modifiers.remove(KeyModifiers::SHIFT);
+        KeyCode::Char(c.to_ascii_uppercase())
+      }
+
+      KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+        modifiers.remove(KeyModifiers::SHIFT);
+        KeyCode::BackTab
+      }
+
+      KeyCode::BackTab => {
+        modifiers.remove(KeyModifiers::SHIFT);
+        KeyCode::BackTab
+      }
+
+      code => code,
+    };
+
+    Self { code, modifiers }
+  }
+
+  /// Returns the code of the key.
+  pub fn code(&self) -> KeyCode {
+    self.code
+  }
+
+  /// Returns the modifiers of the key.
+  pub fn modifiers(&self) -> KeyModifiers {
+    self.modifiers
+  }
+}
+
+impl From<KeyEvent> for Key {
+  fn from(event: KeyEvent) -> Self {
+    Self::new(event.code, event.modifiers)
+  }
+}
+
+impl FromStr for Key {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let key = intuitive_keys::parse(s).map_err(|reason| Error::InvalidKey(s.to_string(), reason))?;
+
+    let modifiers = key.modifiers.into_iter().fold(KeyModifiers::NONE, |modifiers, modifier| {
+      modifiers
+        | match modifier {
+          Modifier::Control => KeyModifiers::CONTROL,
+          Modifier::Alt => KeyModifiers::ALT,
+          Modifier::Shift => KeyModifiers::SHIFT,
+          Modifier::Super => KeyModifiers::SUPER,
+        }
+    });
+
+    let code = match key.code {
+      Code::Char(c) => KeyCode::Char(c),
+      Code::Enter => KeyCode::Enter,
+      Code::Esc => KeyCode::Esc,
+      Code::Tab => KeyCode::Tab,
+      Code::BackTab => KeyCode::BackTab,
+      Code::Backspace => KeyCode::Backspace,
+      Code::Delete => KeyCode::Delete,
+      Code::Insert => KeyCode::Insert,
+      Code::Up => KeyCode::Up,
+      Code::Down => KeyCode::Down,
+      Code::Left => KeyCode::Left,
+      Code::Right => KeyCode::Right,
+      Code::Home => KeyCode::Home,
+      Code::End => KeyCode::End,
+      Code::PageUp => KeyCode::PageUp,
+      Code::PageDown => KeyCode::PageDown,
+      Code::F(n) => KeyCode::F(n),
+    };
+
+    Ok(Self::new(code, modifiers))
+  }
+}
+
+impl fmt::Display for Key {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (modifier, name) in [
+      (KeyModifiers::CONTROL, "ctrl"),
+      (KeyModifiers::ALT, "alt"),
+      (KeyModifiers::SHIFT, "shift"),
+      (KeyModifiers::SUPER, "super"),
+    ] {
+      if self.modifiers.contains(modifier) {
+        write!(f, "{}+", name)?;
+      }
+    }
+
+    match self.code {
+      KeyCode::Char(' ') => write!(f, "space"),
+      KeyCode::Char('+') => write!(f, "plus"),
+      KeyCode::Char(c) => write!(f, "{}", c),
+      KeyCode::F(n) => write!(f, "f{}", n),
+      KeyCode::Enter => write!(f, "enter"),
+      KeyCode::Esc => write!(f, "esc"),
+      KeyCode::Tab => write!(f, "tab"),
+      KeyCode::BackTab => write!(f, "backtab"),
+      KeyCode::Backspace => write!(f, "backspace"),
+      KeyCode::Delete => write!(f, "delete"),
+      KeyCode::Insert => write!(f, "insert"),
+      KeyCode::Up => write!(f, "up"),
+      KeyCode::Down => write!(f, "down"),
+      KeyCode::Left => write!(f, "left"),
+      KeyCode::Right => write!(f, "right"),
+      KeyCode::Home => write!(f, "home"),
+      KeyCode::End => write!(f, "end"),
+      KeyCode::PageUp => write!(f, "pageup"),
+      KeyCode::PageDown => write!(f, "pagedown"),
+      code => write!(f, "{}", format!("{:?}", code).to_ascii_lowercase()),
+    }
+  }
+}
+
+/// A sequence of [`Key`]s, such as `"g g"` or `"ctrl+x ctrl+s"`.
+///
+/// A `Sequence` is parsed from whitespace-separated [`Key`]s.
+///
+/// [`Key`]: struct.Key.html
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Sequence {
+  keys: Vec<Key>,
+}
+
+impl Sequence {
+  /// Returns the keys of the sequence.
+  pub fn keys(&self) -> &[Key] {
+    &self.keys
+  }
+}
+
+impl From<Key> for Sequence {
+  fn from(key: Key) -> Self {
+    Self { keys: vec![key] }
+  }
+}
+
+impl FromStr for Sequence {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let keys = s.split_whitespace().map(Key::from_str).collect::<Result<Vec<_>>>()?;
+
+    if keys.is_empty() {
+      return Err(Error::InvalidKey(s.to_string(), String::from("empty key sequence")));
+    }
+
+    Ok(Self { keys })
+  }
+}
+
+impl fmt::Display for Sequence {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, key) in self.keys.iter().enumerate() {
+      if i > 0 {
+        write!(f, " ")?;
+      }
+
+      write!(f, "{}", key)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_keys() {
+    let key = |s: &str| s.parse::<Key>().unwrap();
+
+    assert_eq!(key("ctrl+x"), Key::new(KeyCode::Char('x'), KeyModifiers::CONTROL));
+    assert_eq!(key("shift+g"), key("G"));
+    assert_eq!(key("shift+tab"), key("backtab"));
+    assert_eq!(
+      key("Ctrl+Alt+Left"),
+      Key::new(KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::ALT)
+    );
+    assert_eq!(key("ctrl++"), Key::new(KeyCode::Char('+'), KeyModifiers::CONTROL));
+    assert_eq!(key("f12"), Key::new(KeyCode::F(12), KeyModifiers::NONE));
+
+    for invalid in ["", "ctrl+", "hyper+x", "ctrl+ctrl+x", "f13", "escape!"] {
+      assert!(invalid.parse::<Key>().is_err(), "{:?} should not parse", invalid);
+    }
+  }
+
+  #[test]
+  fn on_key_matches_key_strings() {
+    use crate::{event::handler::Propagate, on_key};
+
+    let event = |s: &str| {
+      let key = s.parse::<Key>().unwrap();
+      KeyEvent::new(key.code, key.modifiers)
+    };
+
+    let on_key = on_key! { #[propagate]
+      "ctrl+a" | "home" => (),
+      "shift+tab" => (),
+      "G" if false => (),
+    };
+
+    for s in ["ctrl+a", "home", "backtab", "shift+tab"] {
+      assert_eq!(on_key(event(s)), Propagate::Stop, "{:?} should be handled", s);
+    }
+
+    for s in ["a", "ctrl+b", "tab", "G"] {
+      assert_eq!(on_key(event(s)), Propagate::Next, "{:?} should propagate", s);
+    }
+  }
+
+  #[test]
+  fn on_key_arms_return_from_handler() {
+    use crate::{event::handler::Propagate, on_key};
+
+    let on_key = on_key! {
+      KeyEvent { code: Char(c), .. } => {
+        if c == 'q' {
+          return Propagate::Next;
+        }
+      },
+    };
+
+    assert_eq!(on_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)), Propagate::Next);
+    assert_eq!(on_key(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE)), Propagate::Stop);
+  }
+
+  #[test]
+  fn display_round_trips() {
+    for s in ["ctrl+x ctrl+s", "g g", "alt+shift+left", "space", "ctrl+plus", "f5 G"] {
+      let sequence = s.parse::<Sequence>().unwrap();
+      assert_eq!(sequence.to_string().parse::<Sequence>().unwrap(), sequence);
+    }
+  }
+}
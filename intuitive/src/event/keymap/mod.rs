@@ -0,0 +1,558 @@
+//! Keymaps, for binding key sequences to actions.
+//!
+//! A [`Keymap`] binds [`Sequence`]s of keys, written as strings like `"ctrl+x ctrl+s"` or
+//! `"g g"`, to actions. Bindings can be global, or grouped into named modes, such as
+//! `"normal"` and `"insert"`, of which only one is active at a time. A [`Keymap`] converts
+//! into a [`KeyHandler`], so it can be used anywhere an `on_key` parameter is accepted:
+//! ```rust
+//! # use intuitive::{component, components::Text, event::{self, keymap::Keymap}, render, state::use_state};
+//! #
+//! #[component(Editor)]
+//! fn render() {
+//!   let text = use_state(String::new);
+//!
+//!   let keymap = use_state(|| {
+//!     let keymap = Keymap::new();
+//!     let mode = keymap.mode();
+//!
+//!     keymap
+//!       .bind("ctrl+c", event::quit)
+//!       .bind_in("normal", "i", {
+//!         let mode = mode.clone();
+//!         move || mode.set(String::from("insert"))
+//!       })
+//!       .bind_in("normal", "d d", {
+//!         let text = text.clone();
+//!         move || text.set(String::new())
+//!       })
+//!       .bind_in("insert", "esc", move || mode.set(String::from("normal")))
+//!   });
+//!
+//!   render! {
+//!     Text(text: text.get(), on_key: keymap.get())
+//!   }
+//! }
+//! ```
+//!
+//! # Resolving Sequences
+//! Keys that are a prefix of a bound sequence are held until the sequence is complete. If
+//! a held prefix is itself bound, as with `"g"` and `"g g"`, the binding is ambiguous: the
+//! longer sequence runs if the next key completes it within the [`Keymap::timeout`], and
+//! otherwise the shorter one runs, either when the timeout elapses or when a key that
+//! does not continue the sequence is pressed. Prefixes that are not bound are discarded
+//! when the timeout elapses.
+//!
+//! Bindings of the current mode take precedence over global bindings with the same keys.
+//! Keys that are not bound are propagated, as indicated by [`Propagate::Next`].
+//!
+//! # Help
+//! Bindings can be [described](struct.Keymap.html#method.describe). The descriptions of
+//! the bindings active in the current mode are kept as [`Hint`]s by the [`KeyHandler`] a
+//! [`Keymap`] converts into, in order to be displayed by components such as the
+//! experimental [`Help`].
+//!
+//! # Keeping State
+//! Since a [`Keymap`] holds the keys pressed so far, as well as the current mode, it
+//! should be created once, for example within the initializer of [`use_state`], rather
+//! than on every render.
+//!
+//! # Configuration
+//! In order to let users rebind keys, applications can declare named [`Actions`], and
+//! bind them to keys through a [`Config`]. A user's [`Config`], for example loaded with
+//! [`Config::from_toml`], is [merged](struct.Config.html#method.merge) over the
+//! application's defaults, and [`Keymap::from_config`] reports every unknown action,
+//! invalid key sequence, and conflicting binding in the result:
+//! ```rust
+//! # use intuitive::event::{self, keymap::{Actions, Config, Keymap}};
+//! #
+//! let actions = Actions::new()
+//!   .add("quit", "Quit the application", event::quit)
+//!   .add("help", "Show the help", || ());
+//!
+//! let defaults = Config::new().bind("quit", "ctrl+c").bind("help", "?");
+//! let user = Config::new().bind("help", "ctrl+c");
+//!
+//! let err = Keymap::from_config(&actions, &defaults.merge(user)).err().unwrap();
+//! assert_eq!(
+//!   err.to_string(),
+//!   "keymap: 1 problem(s) with key bindings:\n  [global]: `ctrl+c` is bound to more than one action: `help`, `quit`"
+//! );
+//! ```
+//!
+//! [`Actions`]: struct.Actions.html
+//! [`Config`]: struct.Config.html
+//! [`Config::from_toml`]: struct.Config.html#method.from_toml
+//! [`Keymap`]: struct.Keymap.html
+//! [`Keymap::from_config`]: struct.Keymap.html#method.from_config
+//! [`Keymap::timeout`]: struct.Keymap.html#method.timeout
+//! [`Help`]: ../../components/experimental/help/struct.Help.html
+//! [`Hint`]: ../handler/struct.Hint.html
+//! [`KeyHandler`]: ../type.KeyHandler.html
+//! [`Propagate::Next`]: ../handler/enum.Propagate.html#variant.Next
+//! [`Sequence`]: struct.Sequence.html
+//! [`use_state`]: ../../state/fn.use_state.html
+
+mod config;
+mod key;
+
+use std::{
+  collections::HashMap,
+  sync::Arc,
+  thread,
+  time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+pub use self::{
+  config::{Actions, Config, Problem},
+  key::{Key, Sequence},
+};
+use super::{
+  handler::{Handler, Hint, Propagate},
+  KeyEvent, KeyHandler,
+};
+use crate::state::State;
+
+type Action = Arc<dyn Fn() -> Propagate + 'static + Send + Sync>;
+
+#[derive(Clone)]
+struct Binding {
+  keys: Vec<Key>,
+  action: Action,
+  description: Option<String>,
+}
+
+/// The bindings of a [`Keymap`], which do not change once it is used.
+///
+/// [`Keymap`]: struct.Keymap.html
+#[derive(Clone)]
+struct Bindings {
+  global: Vec<Binding>,
+  modes: HashMap<String, Vec<Binding>>,
+  timeout: Duration,
+
+  /// The mode of the most recent binding, if any, for [`Keymap::describe`].
+  ///
+  /// [`Keymap::describe`]: struct.Keymap.html#method.describe
+  last: Option<Option<String>>,
+}
+
+impl Bindings {
+  /// Returns the bindings active in `mode`, in order of precedence.
+  fn active<'a>(&'a self, mode: &str) -> impl Iterator<Item = &'a Binding> {
+    self.modes.get(mode).into_iter().flatten().chain(&self.global)
+  }
+
+  fn section(&mut self, mode: Option<&str>) -> &mut Vec<Binding> {
+    match mode {
+      Some(mode) => self.modes.entry(mode.to_string()).or_default(),
+      None => &mut self.global,
+    }
+  }
+
+  /// Returns the action bound to exactly `keys` in `mode`.
+  fn exact(&self, mode: &str, keys: &[Key]) -> Option<Action> {
+    self
+      .active(mode)
+      .find(|binding| binding.keys == keys)
+      .map(|binding| binding.action.clone())
+  }
+
+  /// Returns whether a longer sequence starting with `keys` is bound in `mode`.
+  fn is_prefix(&self, mode: &str, keys: &[Key]) -> bool {
+    self
+      .active(mode)
+      .any(|binding| binding.keys.len() > keys.len() && binding.keys.starts_with(keys))
+  }
+}
+
+/// The keys pressed so far, that are a prefix of a bound sequence.
+#[derive(Default)]
+struct Pending {
+  keys: Vec<Key>,
+  generation: usize,
+}
+
+/// A set of key bindings, grouped into modes.
+///
+/// See the [module-level documentation] for details.
+///
+/// [module-level documentation]: index.html
+#[derive(Clone)]
+pub struct Keymap {
+  bindings: Arc<Bindings>,
+  pending: Arc<Mutex<Pending>>,
+  mode: State<String>,
+}
+
+impl Default for Keymap {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Keymap {
+  /// The mode a new `Keymap` starts in.
+  pub const DEFAULT_MODE: &'static str = "normal";
+
+  /// The default time to wait for the next key of an ambiguous or incomplete sequence.
+  pub const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1000);
+
+  /// Creates an empty `Keymap`, in the [`Keymap::DEFAULT_MODE`].
+  ///
+  /// [`Keymap::DEFAULT_MODE`]: #associatedconstant.DEFAULT_MODE
+  pub fn new() -> Self {
+    Self {
+      bindings: Arc::new(Bindings {
+        global: Vec::new(),
+        modes: HashMap::new(),
+        timeout: Self::DEFAULT_TIMEOUT,
+        last: None,
+      }),
+      pending: Arc::default(),
+      mode: State::new(String::from(Self::DEFAULT_MODE)),
+    }
+  }
+
+  /// Binds `keys` to `action` in every mode.
+  ///
+  /// The action may return `()`, or a [`Propagate`] to let the last key of the sequence
+  /// propagate.
+  ///
+  /// # Panics
+  /// Panics if `keys` is not a valid [`Sequence`]. Use [`Keymap::bind_sequence`] to bind
+  /// sequences that were parsed beforehand.
+  ///
+  /// [`Keymap::bind_sequence`]: #method.bind_sequence
+  /// [`Propagate`]: ../handler/enum.Propagate.html
+  /// [`Sequence`]: struct.Sequence.html
+  pub fn bind<F, R>(self, keys: &str, action: F) -> Self
+  where
+    F: Fn() -> R + 'static + Send + Sync,
+    R: Into<Propagate>,
+  {
+    self.bind_sequence(None, parse(keys), action)
+  }
+
+  /// Binds `keys` to `action` in `mode`.
+  ///
+  /// See [`Keymap::bind`] for details.
+  ///
+  /// # Panics
+  /// Panics if `keys` is not a valid [`Sequence`].
+  ///
+  /// [`Keymap::bind`]: #method.bind
+  /// [`Sequence`]: struct.Sequence.html
+  pub fn bind_in<F, R>(self, mode: &str, keys: &str, action: F) -> Self
+  where
+    F: Fn() -> R + 'static + Send + Sync,
+    R: Into<Propagate>,
+  {
+    self.bind_sequence(Some(mode), parse(keys), action)
+  }
+
+  /// Binds `sequence` to `action` in `mode`, or in every mode if `mode` is `None`.
+  ///
+  /// Binding a sequence that is already bound in the same mode replaces the previous
+  /// binding.
+  pub fn bind_sequence<F, R>(mut self, mode: Option<&str>, sequence: Sequence, action: F) -> Self
+  where
+    F: Fn() -> R + 'static + Send + Sync,
+    R: Into<Propagate>,
+  {
+    let bindings = Arc::make_mut(&mut self.bindings);
+    bindings.last = Some(mode.map(String::from));
+
+    let bindings = bindings.section(mode);
+    let keys = sequence.keys().to_vec();
+    bindings.retain(|binding| binding.keys != keys);
+    bindings.push(Binding {
+      keys,
+      action: Arc::new(move || action().into()),
+      description: None,
+    });
+
+    self
+  }
+
+  /// Describes the most recently bound sequence, so that it is listed by [`Keymap::hints`].
+  ///
+  /// ```rust
+  /// # use intuitive::event::{self, keymap::Keymap};
+  /// #
+  /// let keymap = Keymap::new()
+  ///   .bind("ctrl+c", event::quit)
+  ///   .describe("Quit the application");
+  ///
+  /// assert_eq!(keymap.hints()[0].description, "Quit the application");
+  /// ```
+  ///
+  /// # Panics
+  /// Panics if nothing was bound yet.
+  ///
+  /// [`Keymap::hints`]: #method.hints
+  pub fn describe(mut self, description: &str) -> Self {
+    let bindings = Arc::make_mut(&mut self.bindings);
+    let mode = bindings.last.clone().expect("describe called before any bindings");
+
+    let binding = bindings.section(mode.as_deref()).last_mut().expect("no bindings");
+    binding.description = Some(description.to_string());
+
+    self
+  }
+
+  /// Returns the [`Hint`]s of the described bindings that are active in the current mode.
+  ///
+  /// [`Hint`]: ../handler/struct.Hint.html
+  pub fn hints(&self) -> Vec<Hint> {
+    let mode = self.mode.get();
+    let mut hints = Vec::new();
+    let mut seen = Vec::new();
+
+    for binding in self.bindings.active(&mode) {
+      // global bindings are shadowed by bindings of the current mode
+      if seen.contains(&&binding.keys) {
+        continue;
+      }
+
+      seen.push(&binding.keys);
+
+      if let Some(description) = &binding.description {
+        let keys: Vec<String> = binding.keys.iter().map(Key::to_string).collect();
+        hints.push(Hint::new(keys.join(" "), description.as_str()));
+      }
+    }
+
+    hints
+  }
+
+  /// Sets the time to wait for the next key of an ambiguous or incomplete sequence.
+  ///
+  /// Defaults to [`Keymap::DEFAULT_TIMEOUT`].
+  ///
+  /// [`Keymap::DEFAULT_TIMEOUT`]: #associatedconstant.DEFAULT_TIMEOUT
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    Arc::make_mut(&mut self.bindings).timeout = timeout;
+    self
+  }
+
+  /// Sets the mode the `Keymap` starts in.
+  pub fn start_in(self, mode: &str) -> Self {
+    Self {
+      mode: State::new(mode.to_string()),
+      ..self
+    }
+  }
+
+  /// Returns the current mode.
+  ///
+  /// Setting the returned [`State`] switches modes, and can be done from within actions.
+  ///
+  /// [`State`]: ../../state/struct.State.html
+  pub fn mode(&self) -> State<String> {
+    self.mode.clone()
+  }
+
+  /// Returns the keys of an incomplete sequence that were pressed so far.
+  pub fn pending(&self) -> Vec<Key> {
+    self.pending.lock().keys.clone()
+  }
+
+  /// Handles a key event, returning whether it should propagate.
+  pub fn handle(&self, event: KeyEvent) -> Propagate {
+    self.press(Key::from(event), Instant::now())
+  }
+
+  fn press(&self, key: Key, now: Instant) -> Propagate {
+    let mode = self.mode.get();
+    let mut pending = self.pending.lock();
+
+    pending.keys.push(key);
+    pending.generation += 1;
+
+    let exact = self.bindings.exact(&mode, &pending.keys);
+
+    if self.bindings.is_prefix(&mode, &pending.keys) {
+      self.expire(pending.generation, now + self.bindings.timeout);
+      return Propagate::Stop;
+    }
+
+    if let Some(action) = exact {
+      pending.keys.clear();
+      drop(pending);
+
+      return action();
+    }
+
+    // the key is not bound on its own
+    if pending.keys.len() == 1 {
+      pending.keys.clear();
+      return Propagate::Next;
+    }
+
+    // the key does not continue the held prefix, so resolve the prefix and start over
+    pending.keys.pop();
+    let prefix = self.bindings.exact(&mode, &pending.keys);
+    pending.keys.clear();
+    drop(pending);
+
+    if let Some(action) = prefix {
+      action();
+    }
+
+    self.press(key, now)
+  }
+
+  /// Resolves the held prefix at `deadline`, unless another key was pressed since.
+  fn expire(&self, generation: usize, deadline: Instant) {
+    let keymap = self.clone();
+
+    thread::spawn(move || {
+      thread::sleep(deadline.saturating_duration_since(Instant::now()));
+
+      let mut pending = keymap.pending.lock();
+      if pending.generation != generation {
+        return;
+      }
+
+      let action = keymap.bindings.exact(&keymap.mode.get(), &pending.keys);
+      pending.keys.clear();
+      drop(pending);
+
+      if let Some(action) = action {
+        action();
+      }
+    });
+  }
+}
+
+fn parse(keys: &str) -> Sequence {
+  keys.parse().unwrap_or_else(|err| panic!("{}", err))
+}
+
+impl From<Keymap> for KeyHandler {
+  fn from(keymap: Keymap) -> Self {
+    let hints = keymap.hints();
+    let handler = Handler::from(move |event| keymap.handle(event));
+
+    hints
+      .iter()
+      .fold(handler, |handler, hint| handler.hint(&hint.keys, &hint.description))
+  }
+}
+
+impl From<&Keymap> for KeyHandler {
+  fn from(keymap: &Keymap) -> Self {
+    keymap.clone().into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::event::{KeyCode, KeyModifiers};
+
+  fn press(keymap: &Keymap, key: &str) -> Propagate {
+    let key: Key = key.parse().unwrap();
+    keymap.handle(KeyEvent::new(key.code(), key.modifiers()))
+  }
+
+  fn record(log: &State<Vec<&'static str>>, name: &'static str) -> impl Fn() + 'static + Send + Sync {
+    let log = log.clone();
+    move || log.mutate(|log| log.push(name))
+  }
+
+  #[test]
+  fn sequences() {
+    let log = State::new(Vec::new());
+    let keymap = Keymap::new()
+      .bind("ctrl+x ctrl+s", record(&log, "save"))
+      .bind("ctrl+x ctrl+c", record(&log, "quit"));
+
+    assert_eq!(press(&keymap, "ctrl+x"), Propagate::Stop);
+    assert_eq!(keymap.pending(), vec!["ctrl+x".parse().unwrap()]);
+    assert_eq!(press(&keymap, "ctrl+s"), Propagate::Stop);
+
+    // an incomplete sequence is discarded, and the key is handled on its own
+    press(&keymap, "ctrl+x");
+    assert_eq!(press(&keymap, "q"), Propagate::Next);
+    press(&keymap, "ctrl+x");
+    press(&keymap, "ctrl+c");
+
+    assert_eq!(log.get(), vec!["save", "quit"]);
+    assert!(keymap.pending().is_empty());
+  }
+
+  #[test]
+  fn ambiguous_prefixes() {
+    let log = State::new(Vec::new());
+    let keymap = Keymap::new()
+      .timeout(Duration::from_millis(50))
+      .bind("g", record(&log, "g"))
+      .bind("g g", record(&log, "g g"))
+      .bind("j", record(&log, "j"));
+
+    press(&keymap, "g");
+    press(&keymap, "g");
+    assert_eq!(log.get(), vec!["g g"]);
+
+    // a key that does not continue the sequence resolves the prefix first
+    press(&keymap, "g");
+    press(&keymap, "j");
+    assert_eq!(log.get(), vec!["g g", "g", "j"]);
+
+    // the prefix is resolved once the timeout elapses
+    press(&keymap, "g");
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(log.get(), vec!["g g", "g", "j", "g"]);
+  }
+
+  #[test]
+  fn modes() {
+    let log = State::new(Vec::new());
+    let keymap = Keymap::new();
+    let mode = keymap.mode();
+
+    let keymap = keymap
+      .bind("esc", record(&log, "global"))
+      .bind_in("normal", "i", move || mode.set(String::from("insert")))
+      .bind_in("insert", "esc", record(&log, "insert"));
+
+    press(&keymap, "esc");
+    assert_eq!(press(&keymap, "i"), Propagate::Stop);
+    assert_eq!(keymap.mode().get(), "insert");
+
+    // unbound in insert mode
+    assert_eq!(press(&keymap, "i"), Propagate::Next);
+    press(&keymap, "esc");
+
+    assert_eq!(log.get(), vec!["global", "insert"]);
+  }
+
+  #[test]
+  fn hints_follow_mode() {
+    let keymap = Keymap::new()
+      .bind("q", || ())
+      .describe("Quit")
+      .bind("?", || ())
+      .bind_in("insert", "q", || ())
+      .describe("Type q");
+
+    let handler = KeyHandler::from(&keymap);
+    assert_eq!(handler.hints(), [Hint::new("q", "Quit")]);
+
+    keymap.mode().set(String::from("insert"));
+    assert_eq!(keymap.hints(), [Hint::new("q", "Type q")]);
+  }
+
+  #[test]
+  fn propagating_actions() {
+    let keymap = Keymap::new().bind("q", || Propagate::Next);
+
+    assert_eq!(
+      KeyHandler::from(&keymap).handle(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)),
+      Propagate::Next
+    );
+  }
+}
@@ -1,20 +1,33 @@
 //! Primitives for handling and sending events.
 
 mod channel;
+pub(crate) mod custom;
 pub mod handler;
+pub mod keymap;
+pub(crate) mod mouse;
+pub(crate) mod tree;
 
-pub use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use std::any::Any;
+
+pub use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
-pub use self::channel::{quit, re_render};
 pub(crate) use self::channel::{read, start_crossterm_events};
 use self::handler::Handler;
 #[cfg(doc)]
 use self::handler::Propagate;
+pub use self::{
+  channel::{quit, re_render},
+  custom::{emit, use_event},
+  mouse::Gesture,
+  tree::{is_default_prevented, prevent_default},
+};
 use crate::terminal::Rect;
 
 pub(crate) enum Event {
   Mouse(MouseEvent),
   Key(KeyEvent),
+  Paste(String),
+  Custom(Box<dyn Any + Send>),
   Render,
   Quit,
 }
@@ -53,15 +66,18 @@ pub(crate) enum Event {
 /// `on_key`, but allow the user of this component to override this handler. This can
 /// be done using the [`KeyHandler::handle_or`] method:
 /// ```rust
-/// # use intuitive::{element::Element, event::{KeyHandler, KeyEvent}};
+/// # use intuitive::{element::Element, event::{handler::Propagate, KeyHandler, KeyEvent}};
 /// #
 /// struct Frozen {
 ///   on_key: KeyHandler,
 /// }
 ///
 /// impl Element for Frozen {
-///   fn on_key(&self, event: KeyEvent) {
-///     self.on_key.handle_or(event, |event| { /* default functionality here */ })
+///   fn on_key(&self, event: KeyEvent) -> Propagate {
+///     self.on_key.handle_or(event, |event| {
+///       /* default functionality here */
+///       Propagate::Stop
+///     })
 ///   }
 /// }
 /// ```
@@ -105,12 +121,23 @@ pub type KeyHandler = Handler<KeyEvent>;
 /// A handler for [`MouseEvent`]s.
 pub type MouseHandler = Handler<MouseEvent>;
 
+/// A handler for pasted text.
+pub type PasteHandler = Handler<String>;
+
+/// A handler for [`Gesture`]s.
+///
+/// [`Gesture`]: enum.Gesture.html
+pub type GestureHandler = Handler<Gesture>;
+
 /// Check if a mouse event is within a [`Rect`].
+///
+/// The right and bottom edges of the [`Rect`] are excluded, as they are the first
+/// column and row past it.
 pub fn is_within(event: &MouseEvent, rect: Rect) -> bool {
   let (x, y) = (event.column, event.row);
 
-  let x_within = rect.x <= x && x <= rect.x + rect.width;
-  let y_within = rect.y <= y && y <= rect.y + rect.height;
+  let x_within = rect.x <= x && x < rect.x + rect.width;
+  let y_within = rect.y <= y && y < rect.y + rect.height;
 
   x_within && y_within
 }
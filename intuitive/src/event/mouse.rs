@@ -0,0 +1,345 @@
+//! Hit-testing for mouse events.
+//!
+//! Mouse events are sent to the deepest element under the cursor, that is, the last one
+//! drawn there, through the capture and bubble phases described in [`Element`]. Mouse
+//! events are also combined into [`Gesture`]s, which bubble up from the element they
+//! happened on.
+//!
+//! [`Element`]: ../../element/trait.Element.html
+//! [`Gesture`]: enum.Gesture.html
+
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use super::{
+  handler::Propagate,
+  tree::{self, Node},
+  MouseEvent, MouseEventKind,
+};
+
+/// The maximum time between two clicks for them to be a double-click.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+static MOUSE: Mutex<Mouse> = Mutex::new(Mouse::new());
+
+/// A high-level mouse interaction with an element, built from [`MouseEvent`]s.
+///
+/// Gestures are sent to elements through [`Element::on_gesture`].
+///
+/// [`Element::on_gesture`]: ../element/trait.Element.html#method.on_gesture
+/// [`MouseEvent`]: struct.MouseEvent.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gesture {
+  /// A button was pressed and released over the element.
+  Click(MouseEvent),
+  /// The element was clicked twice in quick succession. This is sent after the
+  /// [`Gesture::Click`] of the second click.
+  ///
+  /// [`Gesture::Click`]: #variant.Click
+  DoubleClick(MouseEvent),
+  /// The mouse was dragged after a button was pressed over the element. Drags are sent to
+  /// the element the button was pressed over, even once the cursor leaves it.
+  Drag { start: MouseEvent, event: MouseEvent },
+  /// The cursor moved over the element.
+  Enter(MouseEvent),
+  /// The cursor left the element.
+  Leave(MouseEvent),
+}
+
+/// A button press, kept to detect clicks and drags.
+struct Press {
+  event: MouseEvent,
+  path: Vec<usize>,
+}
+
+/// A click, kept to detect double-clicks.
+struct Click {
+  path: Vec<usize>,
+  time: Instant,
+}
+
+/// The state of the mouse.
+struct Mouse {
+  hovered: Vec<Node>,
+  press: Option<Press>,
+  click: Option<Click>,
+}
+
+impl Mouse {
+  const fn new() -> Self {
+    Self {
+      hovered: Vec::new(),
+      press: None,
+      click: None,
+    }
+  }
+}
+
+fn bubble<F>(chain: &[Node], f: F) -> Propagate
+where
+  F: Fn(&Node) -> Propagate,
+{
+  for node in chain {
+    if f(node) == Propagate::Stop {
+      return Propagate::Stop;
+    }
+  }
+
+  Propagate::Next
+}
+
+/// Sends a mouse event to the deepest element under it, along with any gestures it
+/// completes.
+pub(crate) fn dispatch(event: MouseEvent) -> Propagate {
+  dispatch_at(event, Instant::now())
+}
+
+fn dispatch_at(event: MouseEvent, now: Instant) -> Propagate {
+  let chain = tree::chain_at(&event);
+  let mut mouse = MOUSE.lock();
+
+  // hover
+  let paths: Vec<&Vec<usize>> = chain.iter().map(|node| &node.path).collect();
+  let left: Vec<Node> = mouse.hovered.iter().filter(|node| !paths.contains(&&node.path)).cloned().collect();
+  let entered: Vec<Node> = chain
+    .iter()
+    .filter(|node| !mouse.hovered.iter().any(|other| other.path == node.path))
+    .cloned()
+    .collect();
+  mouse.hovered = chain.clone();
+
+  // clicks and drags
+  let deepest = chain.first().map(|node| node.path.clone());
+  let mut gestures: Vec<(Vec<Node>, Gesture)> = Vec::new();
+
+  match event.kind {
+    MouseEventKind::Down(_) => {
+      mouse.press = deepest.map(|path| Press { event, path });
+    }
+
+    MouseEventKind::Up(_) => {
+      if let Some(press) = mouse.press.take() {
+        if deepest.as_ref() == Some(&press.path) {
+          gestures.push((chain.clone(), Gesture::Click(event)));
+
+          let double = matches!(&mouse.click, Some(click) if click.path == press.path && now - click.time <= DOUBLE_CLICK);
+          if double {
+            gestures.push((chain.clone(), Gesture::DoubleClick(event)));
+            mouse.click = None;
+          } else {
+            mouse.click = Some(Click {
+              path: press.path,
+              time: now,
+            });
+          }
+        }
+      }
+    }
+
+    MouseEventKind::Drag(_) => {
+      if let Some(press) = &mouse.press {
+        gestures.push((tree::chain_to(&press.path), Gesture::Drag { start: press.event, event }));
+      }
+    }
+
+    _ => (),
+  }
+
+  // handlers may trigger re-renders and draws, so they must be called without the lock
+  drop(mouse);
+
+  for node in &left {
+    node.element.on_gesture(node.rect, Gesture::Leave(event));
+  }
+
+  for node in entered.iter().rev() {
+    node.element.on_gesture(node.rect, Gesture::Enter(event));
+  }
+
+  let propagate = tree::dispatch(
+    &chain,
+    |node| node.element.on_mouse_capture(node.rect, event),
+    |node| node.element.on_mouse(node.rect, event),
+  );
+
+  for (chain, gesture) in gestures {
+    bubble(&chain, |node| node.element.on_gesture(node.rect, gesture));
+  }
+
+  propagate
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use super::*;
+  use crate::{
+    element::{Any as AnyElement, Element},
+    event::{KeyModifiers, MouseButton},
+    state::State,
+    terminal::{Frame, Rect},
+  };
+
+  struct Recorder {
+    name: &'static str,
+    log: State<Vec<String>>,
+    propagate: Propagate,
+    children: Vec<(Rect, AnyElement)>,
+  }
+
+  impl Element for Recorder {
+    fn draw(&self, _rect: Rect, frame: &mut Frame) {
+      for (rect, child) in &self.children {
+        child.draw(*rect, frame);
+      }
+    }
+
+    fn on_mouse(&self, _rect: Rect, event: MouseEvent) -> Propagate {
+      self.log.mutate(|log| log.push(format!("{} {:?}", self.name, event.kind)));
+      self.propagate
+    }
+
+    fn on_gesture(&self, _rect: Rect, gesture: Gesture) -> Propagate {
+      let gesture = match gesture {
+        Gesture::Click(_) => "click",
+        Gesture::DoubleClick(_) => "double-click",
+        Gesture::Drag { .. } => "drag",
+        Gesture::Enter(_) => "enter",
+        Gesture::Leave(_) => "leave",
+      };
+
+      self.log.mutate(|log| log.push(format!("{} {}", self.name, gesture)));
+      self.propagate
+    }
+  }
+
+  fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+    MouseEvent {
+      kind,
+      column,
+      row,
+      modifiers: KeyModifiers::NONE,
+    }
+  }
+
+  /// Draws a `root` element with a `left` and a `right` child, each 10 columns wide.
+  fn draw(log: &State<Vec<String>>) {
+    let element = |name, propagate, children| {
+      AnyElement::new(Recorder {
+        name,
+        log: log.clone(),
+        propagate,
+        children,
+      })
+    };
+
+    let left = element("left", Propagate::Next, vec![]);
+    let right = element("right", Propagate::Stop, vec![]);
+    let root = element(
+      "root",
+      Propagate::Stop,
+      vec![(Rect::new(0, 0, 10, 10), left), (Rect::new(10, 0, 10, 10), right)],
+    );
+
+    tree::draw(&root, Rect::new(0, 0, 20, 10));
+  }
+
+  fn take(log: &State<Vec<String>>) -> Vec<String> {
+    let taken = log.get();
+    log.set(Vec::new());
+
+    taken
+  }
+
+  #[test]
+  #[serial]
+  fn bubbles_to_ancestors() {
+    *MOUSE.lock() = Mouse::new();
+
+    let log = State::new(Vec::new());
+    draw(&log);
+
+    // the right edge is exclusive
+    dispatch(mouse(MouseEventKind::Moved, 10, 3));
+    assert_eq!(take(&log), vec!["root enter", "right enter", "right Moved"]);
+
+    dispatch(mouse(MouseEventKind::Moved, 9, 3));
+    assert_eq!(take(&log), vec!["right leave", "left enter", "left Moved", "root Moved"]);
+  }
+
+  #[test]
+  #[serial]
+  fn clicks_and_drags() {
+    *MOUSE.lock() = Mouse::new();
+
+    let log = State::new(Vec::new());
+    draw(&log);
+    dispatch(mouse(MouseEventKind::Moved, 12, 3));
+    take(&log);
+
+    let start = Instant::now();
+    dispatch_at(mouse(MouseEventKind::Down(MouseButton::Left), 12, 3), start);
+    dispatch_at(mouse(MouseEventKind::Up(MouseButton::Left), 12, 3), start);
+    dispatch_at(mouse(MouseEventKind::Down(MouseButton::Left), 12, 3), start);
+    dispatch_at(mouse(MouseEventKind::Up(MouseButton::Left), 12, 3), start + DOUBLE_CLICK / 2);
+    assert_eq!(
+      take(&log),
+      vec![
+        "right Down(Left)",
+        "right Up(Left)",
+        "right click",
+        "right Down(Left)",
+        "right Up(Left)",
+        "right click",
+        "right double-click",
+      ]
+    );
+
+    // drags are sent to the pressed element, and releasing elsewhere does not click
+    dispatch(mouse(MouseEventKind::Down(MouseButton::Left), 12, 3));
+    dispatch(mouse(MouseEventKind::Drag(MouseButton::Left), 5, 3));
+    dispatch(mouse(MouseEventKind::Up(MouseButton::Left), 5, 3));
+    assert_eq!(
+      take(&log),
+      vec![
+        "right Down(Left)",
+        "right leave",
+        "left enter",
+        "left Drag(Left)",
+        "root Drag(Left)",
+        "right drag",
+        "left Up(Left)",
+        "root Up(Left)",
+      ]
+    );
+  }
+
+  #[test]
+  fn on_mouse_matches_shorthands() {
+    let on_mouse = crate::on_mouse! { #[propagate]
+      left_click | scroll_up => (),
+      right_drag => return Propagate::Stop,
+      MouseEvent { kind: MouseEventKind::Down(MouseButton::Middle), column, .. } if column > 5 => (),
+    };
+
+    for kind in [
+      MouseEventKind::Down(MouseButton::Left),
+      MouseEventKind::ScrollUp,
+      MouseEventKind::Drag(MouseButton::Right),
+    ] {
+      assert_eq!(on_mouse(mouse(kind, 0, 0)), Propagate::Stop, "{:?} should be handled", kind);
+    }
+
+    for kind in [
+      MouseEventKind::Down(MouseButton::Right),
+      MouseEventKind::ScrollDown,
+      MouseEventKind::Down(MouseButton::Middle),
+    ] {
+      assert_eq!(on_mouse(mouse(kind, 0, 0)), Propagate::Next, "{:?} should propagate", kind);
+    }
+
+    assert_eq!(on_mouse(mouse(MouseEventKind::Down(MouseButton::Middle), 6, 0)), Propagate::Stop);
+  }
+}
@@ -0,0 +1,461 @@
+//! The tree of elements drawn in the last frame, through which events are dispatched.
+//!
+//! Every [`element::Any`] that is drawn records the [`Rect`] it was drawn into, along with
+//! its parent. Events are dispatched along the path from the root to a target element,
+//! first through a capture phase from the root down to the target, and then through a
+//! bubble phase from the target back up to the root. Either phase stops as soon as an
+//! element returns [`Propagate::Stop`].
+//!
+//! [`element::Any`]: ../../element/struct.Any.html
+//! [`Propagate::Stop`]: ../handler/enum.Propagate.html#variant.Stop
+//! [`Rect`]: ../../terminal/struct.Rect.html
+
+use std::{
+  mem,
+  sync::atomic::{AtomicBool, Ordering},
+};
+
+use parking_lot::Mutex;
+
+use super::{handler::Propagate, is_within, KeyEvent, MouseEvent};
+use crate::{element::Any as AnyElement, terminal::Rect};
+
+static TREE: Mutex<Tree> = Mutex::new(Tree::new());
+static DEFAULT_PREVENTED: AtomicBool = AtomicBool::new(false);
+
+/// Why key events are routed towards an element, in increasing priority.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Route {
+  /// The element is a focused [`Focusable`].
+  ///
+  /// [`Focusable`]: ../../components/struct.Focusable.html
+  Focus,
+  /// The element is shown by a [`Modal`].
+  ///
+  /// [`Modal`]: ../../components/experimental/modal/struct.Modal.html
+  Modal,
+}
+
+/// An element that was drawn, identified across frames by its path from the root.
+#[derive(Clone)]
+pub(crate) struct Node {
+  pub rect: Rect,
+  pub element: AnyElement,
+  pub path: Vec<usize>,
+
+  parent: Option<usize>,
+  children: Vec<usize>,
+  route: Option<(Route, usize)>,
+}
+
+/// The elements of the last drawn frame.
+struct Tree {
+  nodes: Vec<Node>,
+  drawing: Vec<Node>,
+  stack: Vec<usize>,
+}
+
+impl Tree {
+  const fn new() -> Self {
+    Self {
+      nodes: Vec::new(),
+      drawing: Vec::new(),
+      stack: Vec::new(),
+    }
+  }
+
+  /// Returns the node at `idx`, followed by its ancestors.
+  fn ancestors(&self, mut idx: Option<usize>) -> Vec<Node> {
+    let mut chain = Vec::new();
+    while let Some(i) = idx {
+      chain.push(self.nodes[i].clone());
+      idx = self.nodes[i].parent;
+    }
+
+    chain
+  }
+
+  /// Returns the target of key events, followed by its ancestors.
+  ///
+  /// Starting from the root, key events are routed to the child that was marked through
+  /// [`route`], or otherwise to the only child of an element. The target is the element
+  /// where this stops.
+  ///
+  /// [`route`]: fn.route.html
+  fn key_chain(&self) -> Vec<Node> {
+    let mut idx = self.nodes.iter().rposition(|node| node.parent.is_none());
+
+    while let Some(i) = idx {
+      let next = match (self.nodes[i].route, self.nodes[i].children.as_slice()) {
+        (Some((_, child)), _) => child,
+        (None, &[child]) => child,
+        _ => break,
+      };
+
+      idx = Some(next);
+    }
+
+    self.ancestors(idx)
+  }
+}
+
+/// Marks the start of drawing a frame.
+pub(crate) fn begin_frame() {
+  let mut tree = TREE.lock();
+
+  tree.drawing.clear();
+  tree.stack.clear();
+}
+
+/// Marks the end of drawing a frame, replacing the elements of the previous one.
+pub(crate) fn end_frame() {
+  let mut tree = TREE.lock();
+
+  tree.nodes = mem::take(&mut tree.drawing);
+}
+
+/// Records that `element` is being drawn into `rect`.
+pub(crate) fn enter(rect: Rect, element: AnyElement) {
+  let mut tree = TREE.lock();
+
+  let parent = tree.stack.last().copied();
+  let idx = tree.drawing.len();
+
+  let path = match parent {
+    Some(parent) => {
+      let mut path = tree.drawing[parent].path.clone();
+      path.push(tree.drawing[parent].children.len());
+      tree.drawing[parent].children.push(idx);
+
+      path
+    }
+
+    None => vec![tree.drawing.iter().filter(|node| node.parent.is_none()).count()],
+  };
+
+  tree.drawing.push(Node {
+    rect,
+    element,
+    path,
+
+    parent,
+    children: Vec::new(),
+    route: None,
+  });
+
+  tree.stack.push(idx);
+}
+
+/// Records that the most recently entered element is done drawing.
+pub(crate) fn exit() {
+  TREE.lock().stack.pop();
+}
+
+/// Routes key events towards the element currently being drawn.
+///
+/// Each of its ancestors routes key events towards it, unless the ancestor already
+/// routes them elsewhere for a higher priority [`Route`].
+///
+/// [`Route`]: enum.Route.html
+pub(crate) fn route(route: Route) {
+  let mut tree = TREE.lock();
+
+  let mut child = match tree.stack.last() {
+    Some(&child) => child,
+    None => return,
+  };
+
+  while let Some(parent) = tree.drawing[child].parent {
+    let node = &mut tree.drawing[parent];
+    if !matches!(node.route, Some((other, _)) if other > route) {
+      node.route = Some((route, child));
+    }
+
+    child = parent;
+  }
+}
+
+/// Returns the deepest element under the mouse event, that is, the last one drawn there,
+/// followed by its ancestors.
+pub(crate) fn chain_at(event: &MouseEvent) -> Vec<Node> {
+  let tree = TREE.lock();
+  let deepest = tree.nodes.iter().rposition(|node| is_within(event, node.rect));
+
+  tree.ancestors(deepest)
+}
+
+/// Returns the element at `path`, followed by its ancestors.
+pub(crate) fn chain_to(path: &[usize]) -> Vec<Node> {
+  let tree = TREE.lock();
+  let idx = tree.nodes.iter().position(|node| node.path == path);
+
+  tree.ancestors(idx)
+}
+
+/// Sends an event along `chain`, which starts at the target element and ends at the root.
+///
+/// `capture` is called from the root down to the target, and then `bubble` is called from
+/// the target back up to the root, until either returns [`Propagate::Stop`].
+///
+/// [`Propagate::Stop`]: ../handler/enum.Propagate.html#variant.Stop
+pub(crate) fn dispatch<C, B>(chain: &[Node], capture: C, bubble: B) -> Propagate
+where
+  C: Fn(&Node) -> Propagate,
+  B: Fn(&Node) -> Propagate,
+{
+  // the default action is only prevented for the event being dispatched, so the flag of
+  // any dispatch this one is nested in is restored once it is done
+  let prevented = DEFAULT_PREVENTED.swap(false, Ordering::SeqCst);
+  let propagate = propagate(chain, capture, bubble);
+  DEFAULT_PREVENTED.store(prevented, Ordering::SeqCst);
+
+  propagate
+}
+
+fn propagate<C, B>(chain: &[Node], capture: C, bubble: B) -> Propagate
+where
+  C: Fn(&Node) -> Propagate,
+  B: Fn(&Node) -> Propagate,
+{
+  for node in chain.iter().rev() {
+    if capture(node) == Propagate::Stop {
+      return Propagate::Stop;
+    }
+  }
+
+  for node in chain {
+    if bubble(node) == Propagate::Stop {
+      return Propagate::Stop;
+    }
+  }
+
+  Propagate::Next
+}
+
+/// Sends a key event to its target element.
+pub(crate) fn dispatch_key(event: KeyEvent) -> Propagate {
+  let chain = TREE.lock().key_chain();
+
+  dispatch(&chain, |node| node.element.on_key_capture(event), |node| node.element.on_key(event))
+}
+
+/// Sends pasted text to the target element of key events.
+pub(crate) fn dispatch_paste(text: &str) -> Propagate {
+  let chain = TREE.lock().key_chain();
+
+  dispatch(
+    &chain,
+    |node| node.element.on_paste_capture(text),
+    |node| node.element.on_paste(text),
+  )
+}
+
+/// Prevents the default action of the event being dispatched, without stopping its
+/// propagation.
+///
+/// Components with a default action for an event, like an input box inserting typed
+/// characters, run it through [`Handler::handle_or`], which skips it once the default
+/// action is prevented. For example, an ancestor can prevent the default action during
+/// the capture phase, while still letting the event bubble up to its own handler. To stop
+/// the propagation of an event instead, a handler returns [`Propagate::Stop`].
+///
+/// [`Handler::handle_or`]: handler/struct.Handler.html#method.handle_or
+/// [`Propagate::Stop`]: handler/enum.Propagate.html#variant.Stop
+pub fn prevent_default() {
+  DEFAULT_PREVENTED.store(true, Ordering::SeqCst);
+}
+
+/// Returns whether the default action of the event being dispatched was prevented
+/// through [`prevent_default`].
+///
+/// [`prevent_default`]: fn.prevent_default.html
+pub fn is_default_prevented() -> bool {
+  DEFAULT_PREVENTED.load(Ordering::SeqCst)
+}
+
+/// Draws `element` into an off-screen frame of size `rect`, recording it as the last
+/// drawn frame.
+#[cfg(test)]
+pub(crate) fn draw(element: &AnyElement, rect: Rect) {
+  use tui::{
+    backend::TestBackend,
+    terminal::{Terminal, TerminalOptions, Viewport},
+  };
+
+  let options = TerminalOptions {
+    viewport: Viewport::fixed(rect),
+  };
+  let backend = TestBackend::new(rect.right(), rect.bottom());
+  let mut terminal = Terminal::with_options(backend, options).expect("terminal");
+
+  begin_frame();
+  element.draw(rect, &mut terminal.get_frame());
+  end_frame();
+}
+
+/// Returns the rect of the deepest element drawn at `(column, row)` in the last frame.
+#[cfg(test)]
+pub(crate) fn rect_at(column: u16, row: u16) -> Option<Rect> {
+  use super::{KeyModifiers, MouseButton, MouseEventKind};
+
+  let event = MouseEvent {
+    kind: MouseEventKind::Down(MouseButton::Left),
+    column,
+    row,
+    modifiers: KeyModifiers::NONE,
+  };
+
+  chain_at(&event).first().map(|node| node.rect)
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use super::*;
+  use crate::{
+    element::Element,
+    event::{KeyCode, KeyModifiers},
+    state::State,
+    terminal::Frame,
+  };
+
+  struct Recorder {
+    name: &'static str,
+    log: State<Vec<String>>,
+    capture: Propagate,
+    children: Vec<AnyElement>,
+    route: Option<Route>,
+  }
+
+  impl Element for Recorder {
+    fn draw(&self, rect: Rect, frame: &mut Frame) {
+      if let Some(route) = self.route {
+        super::route(route);
+      }
+
+      for child in &self.children {
+        child.draw(rect, frame);
+      }
+    }
+
+    fn on_key_capture(&self, _event: KeyEvent) -> Propagate {
+      self.log.mutate(|log| log.push(format!("capture {}", self.name)));
+      self.capture
+    }
+
+    fn on_key(&self, _event: KeyEvent) -> Propagate {
+      self.log.mutate(|log| log.push(format!("bubble {}", self.name)));
+
+      if is_default_prevented() {
+        Propagate::Next
+      } else {
+        Propagate::Stop
+      }
+    }
+  }
+
+  fn recorder(log: &State<Vec<String>>, name: &'static str, children: Vec<AnyElement>) -> Recorder {
+    Recorder {
+      name,
+      log: log.clone(),
+      capture: Propagate::Next,
+      children,
+      route: None,
+    }
+  }
+
+  fn key() -> KeyEvent {
+    KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)
+  }
+
+  #[test]
+  #[serial]
+  fn capture_then_bubble() {
+    let log = State::new(Vec::new());
+
+    let leaf = recorder(&log, "leaf", vec![]);
+    let middle = recorder(&log, "middle", vec![AnyElement::new(leaf)]);
+    let root = AnyElement::new(recorder(&log, "root", vec![AnyElement::new(middle)]));
+
+    draw(&root, Rect::new(0, 0, 10, 10));
+
+    assert_eq!(dispatch_key(key()), Propagate::Stop);
+    assert_eq!(log.get(), vec!["capture root", "capture middle", "capture leaf", "bubble leaf"]);
+  }
+
+  #[test]
+  #[serial]
+  fn capture_stops_and_prevents_default() {
+    let log = State::new(Vec::new());
+
+    let leaf = recorder(&log, "leaf", vec![]);
+    let mut root = recorder(&log, "root", vec![AnyElement::new(leaf)]);
+    root.capture = Propagate::Stop;
+    let root = AnyElement::new(root);
+
+    draw(&root, Rect::new(0, 0, 10, 10));
+
+    assert_eq!(dispatch_key(key()), Propagate::Stop);
+    assert_eq!(log.get(), vec!["capture root"]);
+
+    // preventing the default action during the capture phase keeps propagating
+    let log = State::new(Vec::new());
+
+    struct Preventing(AnyElement);
+
+    impl Element for Preventing {
+      fn draw(&self, rect: Rect, frame: &mut Frame) {
+        self.0.draw(rect, frame);
+      }
+
+      fn on_key_capture(&self, _event: KeyEvent) -> Propagate {
+        prevent_default();
+        Propagate::Next
+      }
+    }
+
+    let leaf = recorder(&log, "leaf", vec![]);
+    let root = AnyElement::new(Preventing(AnyElement::new(leaf)));
+    draw(&root, Rect::new(0, 0, 10, 10));
+
+    assert_eq!(dispatch_key(key()), Propagate::Next);
+    assert_eq!(log.get(), vec!["capture leaf", "bubble leaf"]);
+
+    // the default action is only prevented while the event is dispatched
+    assert!(!is_default_prevented());
+  }
+
+  #[test]
+  #[serial]
+  fn keys_follow_routes() {
+    let log = State::new(Vec::new());
+
+    // without routes, keys stop at elements with several children
+    let left = recorder(&log, "left", vec![]);
+    let right = recorder(&log, "right", vec![]);
+    let root = AnyElement::new(recorder(&log, "root", vec![AnyElement::new(left), AnyElement::new(right)]));
+    draw(&root, Rect::new(0, 0, 10, 10));
+
+    dispatch_key(key());
+    assert_eq!(log.get(), vec!["capture root", "bubble root"]);
+
+    // shown modals take priority over the focused element
+    let log = State::new(Vec::new());
+
+    let mut focused = recorder(&log, "focused", vec![]);
+    focused.route = Some(Route::Focus);
+    let mut modal = recorder(&log, "modal", vec![]);
+    modal.route = Some(Route::Modal);
+    let content = recorder(
+      &log,
+      "content",
+      vec![AnyElement::new(focused), AnyElement::new(recorder(&log, "other", vec![]))],
+    );
+    let root = AnyElement::new(recorder(&log, "root", vec![AnyElement::new(content), AnyElement::new(modal)]));
+    draw(&root, Rect::new(0, 0, 10, 10));
+
+    dispatch_key(key());
+    assert_eq!(log.get(), vec!["capture root", "capture modal", "bubble modal"]);
+  }
+}
@@ -0,0 +1,339 @@
+//! Primitives for moving focus between components.
+//!
+//! Any component rendered inside of a [`Focusable`] can receive focus. Focusable
+//! components are ordered by the order in which they are rendered, and pressing `Tab`
+//! or `Shift+Tab` moves the focus to the next or previous one. While at least one
+//! [`Focusable`] is rendered, `Tab` and `Shift+Tab` are reserved for moving focus.
+//!
+//! # Key Events
+//! Key events target the focused [`Focusable`], and are sent through the capture and
+//! bubble phases described in [`Element`]. Starting from the focused [`Focusable`], they
+//! continue down to its descendants through elements with a single child. If the key
+//! event is not handled, as indicated by a returned [`Propagate::Next`], it bubbles up
+//! through the `on_key` handlers of the enclosing elements, including any enclosing
+//! [`Focusable`]s, and finally to the root component.
+//!
+//! # Styling
+//! Components can query whether they are focused with [`use_focus`]. For example, a
+//! [`Section`] whose border is highlighted when it's focused:
+//! ```rust
+//! # use intuitive::{component, components::{Focusable, Section, Text, VStack}, focus::use_focus, render, style::Color};
+//! #
+//! #[component(Input)]
+//! fn render(title: String) {
+//!   let focus = use_focus();
+//!   let border = if focus.is_focused() { Color::Blue } else { Color::Gray };
+//!
+//!   render! {
+//!     Section(title, border) {
+//!       Text(text: "type here")
+//!     }
+//!   }
+//! }
+//!
+//! #[component(Root)]
+//! fn render() {
+//!   render! {
+//!     VStack() {
+//!       Focusable() { Input(title: "A") }
+//!       Focusable() { Input(title: "B") }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! [`Element`]: ../element/trait.Element.html
+//! [`Focusable`]: ../components/struct.Focusable.html
+//! [`Propagate::Next`]: ../event/handler/enum.Propagate.html#variant.Next
+//! [`Section`]: ../components/struct.Section.html
+//! [`use_focus`]: fn.use_focus.html
+
+use std::mem;
+
+use parking_lot::Mutex;
+
+use crate::{
+  event::{
+    self,
+    handler::{Hint, Propagate},
+    KeyCode, KeyEvent, KeyHandler,
+  },
+  state,
+};
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry::new());
+
+struct Node {
+  parent: Option<usize>,
+  on_key: KeyHandler,
+}
+
+/// The focusable components, in the order in which they were rendered.
+struct Registry {
+  nodes: Vec<Node>,
+  rendering: Vec<Node>,
+  stack: Vec<usize>,
+  focused: usize,
+}
+
+impl Registry {
+  const fn new() -> Self {
+    Self {
+      nodes: Vec::new(),
+      rendering: Vec::new(),
+      stack: Vec::new(),
+      focused: 0,
+    }
+  }
+
+  /// Returns the key handlers of the focused node and its ancestors.
+  fn chain(&self) -> Vec<KeyHandler> {
+    let mut chain = Vec::new();
+    let mut node = self.nodes.get(self.focused).map(|_| self.focused);
+
+    while let Some(idx) = node {
+      chain.push(self.nodes[idx].on_key.clone());
+      node = self.nodes[idx].parent;
+    }
+
+    chain
+  }
+}
+
+/// A handle to the focus state of the nearest enclosing [`Focusable`].
+///
+/// [`Focusable`]: ../components/struct.Focusable.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Focus {
+  index: Option<usize>,
+}
+
+impl Focus {
+  /// Returns whether the [`Focusable`] is focused.
+  ///
+  /// [`Focusable`]: ../components/struct.Focusable.html
+  pub fn is_focused(&self) -> bool {
+    self.index == Some(REGISTRY.lock().focused)
+  }
+
+  /// Moves the focus to the [`Focusable`], and triggers a re-render.
+  ///
+  /// [`Focusable`]: ../components/struct.Focusable.html
+  pub fn focus(&self) {
+    if let Some(index) = self.index {
+      REGISTRY.lock().focused = index;
+
+      event::re_render().expect("re_render");
+    }
+  }
+}
+
+/// Returns the [`Focus`] of the nearest enclosing [`Focusable`].
+///
+/// Like [`use_modal`], this may only be called within a call to [`Component::render`].
+/// If there is no enclosing [`Focusable`], the returned [`Focus`] is never focused.
+///
+/// [`Component::render`]: ../components/trait.Component.html#tymethod.render
+/// [`Focus`]: struct.Focus.html
+/// [`Focusable`]: ../components/struct.Focusable.html
+/// [`use_modal`]: ../components/experimental/modal/fn.use_modal.html
+pub fn use_focus() -> Focus {
+  Focus {
+    index: REGISTRY.lock().stack.last().copied(),
+  }
+}
+
+/// Moves the focus to the next [`Focusable`], wrapping around to the first one.
+///
+/// [`Focusable`]: ../components/struct.Focusable.html
+pub fn next() {
+  cycle(true);
+}
+
+/// Moves the focus to the previous [`Focusable`], wrapping around to the last one.
+///
+/// [`Focusable`]: ../components/struct.Focusable.html
+pub fn previous() {
+  cycle(false);
+}
+
+fn cycle(forward: bool) {
+  let mut registry = REGISTRY.lock();
+
+  let len = registry.nodes.len();
+  if len == 0 {
+    return;
+  }
+
+  registry.focused = if forward {
+    (registry.focused + 1) % len
+  } else {
+    (registry.focused + len - 1) % len
+  };
+
+  drop(registry);
+  event::re_render().expect("re_render");
+}
+
+/// Returns the [`Hint`]s of the `on_key` handlers of the focused [`Focusable`] and its
+/// ancestors, starting with the focused one.
+///
+/// Hints for keys that were already described by a more deeply nested [`Focusable`] are
+/// skipped, since that one receives those keys first. Since [`Focusable`]s are registered
+/// while rendering, this returns the hints as of the last completed render.
+///
+/// [`Focusable`]: ../components/struct.Focusable.html
+/// [`Hint`]: ../event/handler/struct.Hint.html
+pub fn hints() -> Vec<Hint> {
+  let mut hints: Vec<Hint> = Vec::new();
+
+  for on_key in REGISTRY.lock().chain() {
+    for hint in on_key.hints() {
+      if !hints.iter().any(|other| other.keys == hint.keys) {
+        hints.push(hint.clone());
+      }
+    }
+  }
+
+  hints
+}
+
+/// Registers a focusable component that is being rendered, returning its index.
+pub(crate) fn enter() -> usize {
+  let index = REGISTRY.lock().rendering.len();
+
+  state::effect(|| {
+    let mut registry = REGISTRY.lock();
+
+    let parent = registry.stack.last().copied();
+    registry.rendering.push(Node {
+      parent,
+      on_key: KeyHandler::default(),
+    });
+
+    let index = registry.rendering.len() - 1;
+    registry.stack.push(index);
+  });
+
+  index
+}
+
+/// Marks the end of rendering the focusable component at `index`.
+pub(crate) fn exit(index: usize, on_key: KeyHandler) {
+  state::effect(move || {
+    let mut registry = REGISTRY.lock();
+
+    registry.rendering[index].on_key = on_key.clone();
+    registry.stack.pop();
+  });
+}
+
+/// Describes the focus as seen by a component being rendered, through the number of
+/// focusable components rendered before it, the index of the enclosing one, and the
+/// index of the focused one.
+pub(crate) type Fingerprint = (usize, Option<usize>, usize);
+
+/// Returns the [`Fingerprint`] of the focus for the component being rendered.
+///
+/// [`Fingerprint`]: type.Fingerprint.html
+pub(crate) fn fingerprint() -> Fingerprint {
+  let registry = REGISTRY.lock();
+
+  (registry.rendering.len(), registry.stack.last().copied(), registry.focused)
+}
+
+/// Marks the end of a render, replacing the previously rendered focusable components.
+pub(crate) fn render_done() {
+  let mut registry = REGISTRY.lock();
+
+  registry.nodes = mem::take(&mut registry.rendering);
+  registry.stack.clear();
+  registry.focused = registry.focused.min(registry.nodes.len().saturating_sub(1));
+}
+
+/// Moves the focus if the key event is `Tab` or `Shift+Tab`, and a focusable component
+/// is rendered.
+pub(crate) fn dispatch(event: KeyEvent) -> Propagate {
+  if REGISTRY.lock().nodes.is_empty() {
+    return Propagate::Next;
+  }
+
+  match event.code {
+    KeyCode::Tab => next(),
+    KeyCode::BackTab => previous(),
+
+    _ => return Propagate::Next,
+  }
+
+  Propagate::Stop
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use super::*;
+  use crate::{
+    components::{Focusable, Text},
+    element::Any as AnyElement,
+    event::{tree, KeyModifiers},
+    render,
+    state::State,
+    terminal::Rect,
+  };
+
+  #[test]
+  #[serial]
+  fn tab_cycles_focus() {
+    *REGISTRY.lock() = Registry::new();
+
+    for _ in 0..2 {
+      let index = enter();
+      exit(index, KeyHandler::default());
+    }
+
+    render_done();
+
+    dispatch(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+    assert_eq!(REGISTRY.lock().focused, 1);
+
+    dispatch(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+    assert_eq!(REGISTRY.lock().focused, 0);
+
+    dispatch(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT));
+    assert_eq!(REGISTRY.lock().focused, 1);
+  }
+
+  #[test]
+  #[serial]
+  fn keys_bubble_up() {
+    *REGISTRY.lock() = Registry::new();
+
+    let received = State::new(Vec::new());
+    let handler = |name: &'static str, propagate: Propagate| {
+      let received = received.clone();
+      KeyHandler::from(move |_| {
+        received.mutate(|received| received.push(name));
+        propagate
+      })
+    };
+
+    let (outer, inner) = (handler("outer", Propagate::Stop), handler("inner", Propagate::Next));
+    let root: AnyElement = render! {
+      Focusable(on_key: outer) {
+        Focusable(on_key: inner) {
+          Text()
+        }
+      }
+    };
+
+    render_done();
+
+    // focus the inner node
+    next();
+    tree::draw(&root, Rect::new(0, 0, 10, 10));
+
+    assert_eq!(tree::dispatch_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)), Propagate::Stop);
+    assert_eq!(received.get(), vec!["inner", "outer"]);
+  }
+}
@@ -73,8 +73,8 @@
 //!
 //! Both of these are discussed in depth in the [`components`] module documentation. Other
 //! useful resources are:
-//! - The documentation for the [`render!`] and [`on_key!`] macros, as they are often used
-//!   when writing components.
+//! - The documentation for the [`render!`], [`on_key!`], and [`on_mouse!`] macros, as
+//!   they are often used when writing components.
 //! - The [recipes] section of the [`components`] module documentation, describing ways to
 //!   achieve common UI interactions.
 //! - The [examples] directory in the repository, which contains complete examples of simple
@@ -92,6 +92,7 @@
 //! [`component` attribute macro]: attr.component.html
 //! [`render!`]: macro.render.html
 //! [`on_key!`]: macro.on_key.html
+//! [`on_mouse!`]: macro.on_mouse.html
 //! [`Component`]: components/trait.Component.html
 //! [`components`]: components/index.html
 //! [`Element`]: element/trait.Element.html
@@ -108,6 +109,7 @@ pub mod components;
 pub mod element;
 pub mod error;
 pub mod event;
+pub mod focus;
 pub mod state;
 pub mod style;
 pub mod terminal;
@@ -145,22 +147,70 @@ pub mod text;
 /// # Parameters
 /// If the `render` function contains parameters, these will become parameters to the
 /// generated component. These parameters can later be supplied when using the generated
-/// component in a [`render!`] macro. The parameters' types **must** implement [`Default`],
-/// as the generated component derives [`Default`]. If you need more control over the
-/// default values of the parameters, consider implementing the [`Component`] trait instead
-/// of using the `#[component(..)]` attribute macro.
+/// component in a [`render!`] macro. Parameters that are omitted take their default
+/// values, which are given by [`Default`], unless the parameter has a `#[default(..)]`
+/// attribute. The expression in the attribute is converted into the parameter's type, just
+/// like a value passed in a [`render!`] macro. For example,
+/// ```rust
+/// # use intuitive::{component, components::{stack::Flex, HStack, Section, Text}, render, style::{Color, Style}};
+/// #
+/// #[component(Panel)]
+/// pub fn render(
+///   title: String,
+///   #[default(Color::Gray)] border: Style,
+///   #[default([Flex::Grow(1), Flex::Grow(2)])] flex: Vec<Flex>,
+/// ) {
+///   render! {
+///     HStack(flex: flex.clone()) {
+///       Section(title, border: *border)
+///       Text(text: "Details")
+///     }
+///   }
+/// }
+/// ```
+/// creates a `Panel` with a gray border when no `border` is passed. Types of parameters
+/// without a `#[default(..)]` attribute **must** implement [`Default`].
+///
+/// ## Optional Parameters
+/// Parameters of type `Option<T>` are optional, and are `None` when omitted. When passing
+/// an optional parameter, the value does not need to be wrapped in `Some`, as anything that
+/// can be converted into `T` can be passed directly, as well as an `Option<T>`:
+/// ```rust
+/// # use intuitive::{component, components::{Any as AnyComponent, Section, Text}, render};
+/// #
+/// #[component(Message)]
+/// pub fn render(text: String, title: Option<String>) {
+///   match title {
+///     Some(title) => render! {
+///       Section(title: title.clone()) {
+///         Text(text: text.clone())
+///       }
+///     },
+///     None => render! {
+///       Text(text: text.clone())
+///     },
+///   }
+/// }
+///
+/// let _: AnyComponent = render! { Message(text: "Hello", title: "Greeting") };
+/// let _: AnyComponent = render! { Message(text: "Hello", title: None) };
+/// let _: AnyComponent = render! { Message(text: "Hello") };
+/// ```
+///
+/// Optional parameters are stored in the generated component as an [`Optional`], and
+/// passed to the `render` function as an `Option<T>`.
 ///
 /// # Managing State
 /// State in functional components is managed similarly to how they are in [React],
 /// using the [`use_state`] hook. Refer to the [`use_state`] documentation for details.
 ///
 /// # Handling Key Events
-/// In functional components, key events are sent to the component at the root of the
-/// returned [`render!`] macro invocation. This means that in the example above, the
-/// key event will be sent to an instance of the [`Centered`] component. However,
-/// most components forward their key events to their children (especially those that
-/// have only a single child), and therefore the `on_key` handler could have been
-/// provided to any of the [`Centered`], [`Section`], or [`Text`] components above.
+/// Key events are sent down from the root through components with a single child, or
+/// towards the focused component, and then bubble up through the `on_key` handlers of
+/// their ancestors, as described in [`Element`]. This means that in the example above,
+/// the key event will be sent to the [`Text`] component, and then bubble up to the
+/// [`Section`] and [`Centered`] components, and therefore the `on_key` handler could have
+/// been provided to any of them.
 ///
 /// # Generics
 /// When requiring generics, for example when accepting a variable number of children,
@@ -188,6 +238,29 @@ pub mod text;
 /// }
 /// ```
 ///
+/// # Memoization
+/// By default, a component is rendered again every time its parent is. Passing `memo` to
+/// the attribute, as in `#[component(Name, memo)]`, skips rendering the component when its
+/// parameters are equal to those of the previous render, and nothing it read while
+/// rendering changed, reusing the previously rendered element instead. For example,
+/// ```rust
+/// # use intuitive::{component, components::{Section, Text}, render, state::State};
+/// #
+/// #[component(Summary, memo)]
+/// pub fn render(title: String, items: State<Vec<String>>) {
+///   render! {
+///     Section(title: title.clone()) {
+///       Text(text: format!("{} items", items.get().len()))
+///     }
+///   }
+/// }
+/// ```
+/// is only rendered again when `title` changes, or when `items` is mutated. Memoized
+/// components derive [`Clone`] and [`PartialEq`], so the types of their parameters must
+/// implement them. [`State`]s, handlers, and components are only equal to their clones,
+/// so a component receiving children or handlers created while rendering its parent is
+/// rendered every time. See [`use_memo`] for what counts as being read while rendering.
+///
 /// # Generated Component
 /// The generated component is a structure that implements the [`Component`] trait. It
 /// also has a an associated function `new() -> component::Any` that is used to create the
@@ -204,12 +277,18 @@ pub mod text;
 ///   is called `render` and the return type is left empty.
 ///
 /// [`Centered`]: components/struct.Centered.html
+/// [`Clone`]: https://doc.rust-lang.org/std/clone/trait.Clone.html
 /// [`Component`]: components/trait.Component.html
 /// [`Default`]: https://doc.rust-lang.org/std/default/trait.Default.html
+/// [`Element`]: element/trait.Element.html
+/// [`Optional`]: components/param/struct.Optional.html
+/// [`PartialEq`]: https://doc.rust-lang.org/std/cmp/trait.PartialEq.html
 /// [React]: https://reactjs.org/
 /// [`render!`]: macro.render.html
 /// [`Section`]: components/struct.Section.html
+/// [`State`]: state/struct.State.html
 /// [`Text`]: components/struct.Text.html
+/// [`use_memo`]: state/fn.use_memo.html
 /// [`use_state`]: state/fn.use_state.html
 pub use intuitive_macros::component;
 /// Helper macro for creating key handlers.
@@ -223,7 +302,8 @@ pub use intuitive_macros::component;
 /// In addition to the above, this macro also:
 /// - implicitly introduces the `|event|` closure parameter
 /// - adds the catch-all `_ => ()` case to the `match` expression
-/// - returns [`event::handler::Propagate::Stop`]
+/// - returns [`event::handler::Propagate::Stop`], unless the matched arm evaluates to a
+///   [`event::handler::Propagate`], in which case that is returned
 ///
 /// # Usage
 /// An example usage looks like the following:
@@ -247,17 +327,116 @@ pub use intuitive_macros::component;
 ///   let text = text.clone();
 ///
 ///   move |event| {
-///     use intuitive::event::{self, KeyEvent, KeyCode::*};
+///     use intuitive::event::{self, KeyEvent, KeyCode::*, handler::Propagate};
 ///
 ///     match event {
 ///       KeyEvent { code: Char(c), .. } => text.mutate(|text| text.push(c)),
 ///       KeyEvent { code: Char(c), .. } => text.mutate(|text| text.pop()),
 ///       _ => (),
 ///     };
-///   };
+///
+///     Propagate::Stop
+///   }
+/// };
+/// ```
+///
+/// # Key Strings
+/// Instead of a pattern, an arm can match a key string such as `"ctrl+a"`, using the
+/// same syntax as a [`event::keymap::Key`]. Several keys are matched with `|`, and arms
+/// matching key strings can have guards:
+/// ```rust
+/// # use intuitive::{state::use_state, on_key};
+/// #
+/// let index = use_state(|| 0);
+///
+/// let on_key = on_key! { [index]
+///   "up" | "k" if index.get() > 0 => index.update(|index| index - 1),
+///   "down" | "j" => index.update(|index| index + 1),
+///   "ctrl+a" | "home" => index.set(0),
+///   "esc" => event::quit(),
+/// };
+/// ```
+///
+/// Key strings are parsed when the macro is expanded, so an invalid key is a compile
+/// error:
+/// ```compile_fail
+/// # use intuitive::on_key;
+/// #
+/// let on_key = on_key! {
+///   "ctrl+escp" => (),
+/// };
+/// ```
+///
+/// # Propagating By Default
+/// Keys that are not matched by any arm are not propagated. Adding `#[propagate]` before
+/// the arms, and before any list of captured state, makes unmatched keys propagate
+/// instead:
+/// ```rust
+/// # use intuitive::{state::use_state, on_key};
+/// #
+/// let text = use_state(String::new);
+///
+/// let on_key = on_key! { #[propagate] [text]
+///   KeyEvent { code: Char(c), .. } => text.mutate(|text| text.push(c)),
+///   "backspace" => text.mutate(|text| text.pop()),
 /// };
 /// ```
 pub use intuitive_macros::on_key;
+/// Helper macro for creating mouse handlers.
+///
+/// # Details
+/// This macro mirrors [`on_key!`], constructing an [`event::MouseHandler`] where:
+/// - [`event`], [`event::MouseEvent`], [`event::MouseEventKind`], [`event::MouseButton`],
+///   [`event::KeyModifiers`], and [`event::handler::Propagate`] are brought into scope
+/// - [`state::State`]s listed in brackets are cloned before being moved into the handler
+/// - the implicit `event` closure parameter is immediately `match`ed
+/// - unmatched events return [`event::handler::Propagate::Stop`], or
+///   [`event::handler::Propagate::Next`] if the arms are preceded by `#[propagate]`
+///
+/// # Shorthands
+/// Instead of a `MouseEvent` pattern, an arm can match one of the following shorthands,
+/// which match on the event's `kind` only:
+/// - `left_click`, `right_click`, `middle_click`, for buttons being pressed
+/// - `left_release`, `right_release`, `middle_release`, for buttons being released
+/// - `left_drag`, `right_drag`, `middle_drag`, for the mouse moving while a button is held
+/// - `scroll_up`, `scroll_down`, and `moved`
+///
+/// Shorthands can be combined with `|` and guards, and mixed with regular patterns:
+/// ```rust
+/// # use intuitive::{component, components::{Section, Text}, on_mouse, render, state::use_state};
+/// #
+/// #[component(Counter)]
+/// fn render() {
+///   let count = use_state(|| 0);
+///
+///   let on_mouse = on_mouse! { [count]
+///     left_click | scroll_up => count.update(|count| count + 1),
+///     scroll_down if count.get() > 0 => count.update(|count| count - 1),
+///     MouseEvent { kind: MouseEventKind::Down(_), modifiers, .. } if modifiers.contains(KeyModifiers::CONTROL) => {
+///       count.set(0)
+///     }
+///   };
+///
+///   render! {
+///     Section(title: "Counter", on_mouse) {
+///       Text(text: count.get().to_string())
+///     }
+///   }
+/// }
+/// ```
+///
+/// Identifiers that look like a shorthand but are not one, such as `left_clik`, are
+/// reported at compile time, rather than binding the event and matching every event:
+/// ```compile_fail
+/// # use intuitive::on_mouse;
+/// #
+/// let on_mouse = on_mouse! {
+///   left_clik => (),
+/// };
+/// ```
+///
+/// [`on_key!`]: macro.on_key.html
+pub use intuitive_macros::on_mouse;
 /// Macro for rendering components.
 ///
 /// # Usage
@@ -292,10 +471,52 @@ pub use intuitive_macros::on_key;
 ///
 /// ## Automatic Parameter Conversion
 /// When passing parameters to components within a `render!` macro invocation, an implicit
-/// [`TryInto::try_into`] call is made for each parameter. This means that you can omit
+/// [`Into::into`] call is made for each parameter. This means that you can omit
 /// any `.into()` calls when passing parameters to components. This is very useful when
 /// working with [`Spans`] and [`Style`], as they implement [`From`] from a variety
-/// of types.
+/// of types. Optional parameters of functional components additionally accept anything
+/// that can be converted into the type they wrap, as described in [`param`]. Numeric
+/// literals without a suffix take their type from the parameter, so `width: 5` can be
+/// passed to parameters of type `u16` or `Option<u16>`.
+///
+/// Parameters of the wrong type, and parameters that the component does not have, are
+/// reported at compile time, pointing at the offending argument:
+/// ```compile_fail
+/// # use intuitive::{components::{Any as AnyComponent, Section}, render};
+/// #
+/// let _: AnyComponent = render! {
+///   Section(titel: "Misspelled")
+/// };
+/// ```
+/// ```compile_fail
+/// # use intuitive::{components::{Any as AnyComponent, Text}, render};
+/// #
+/// let count = 42;
+///
+/// let _: AnyComponent = render! {
+///   Text(text: count)
+/// };
+/// ```
+///
+/// ## Fallible Parameter Conversion
+/// Conversions that can fail are opted into by writing `try` before the value, in which
+/// case [`TryInto::try_into`] is called instead, panicking if the conversion fails:
+/// ```rust
+/// # use intuitive::{component, components::{Any as AnyComponent, Text}, render};
+/// #
+/// #[component(Progress)]
+/// fn render(done: u16) {
+///   render! {
+///     Text(text: format!("{} done", done))
+///   }
+/// }
+///
+/// let items = vec!["a", "b", "c"];
+///
+/// let _: AnyComponent = render! {
+///   Progress(done: try items.len())
+/// };
+/// ```
 ///
 /// # Children
 /// Children to a component come after the component surrounded by braces (`{ ... }`).
@@ -304,10 +525,91 @@ pub use intuitive_macros::on_key;
 ///
 /// Children are passed as arrays (`[AnyComponent; N]`), so components specify exactly
 /// how many children they take in. Some components, like `VStack` and `HStack` take
-/// in a variable number of children, while some, like `Section`, only accept a single
-/// child component.
+/// in a variable number of children, through a [`children::List`], while some, like
+/// `Section`, only accept a single child component.
+///
+/// ## Spreading Children
+/// Components taking a [`children::List`] can also be given a collection of children
+/// whose length is only known at runtime, by spreading any `IntoIterator` of
+/// [`components::Any`] with `..`, alongside other children. For example,
+/// ```rust
+/// # use intuitive::{components::{Any as AnyComponent, Section, Text, VStack}, render};
+/// #
+/// let items = vec!["apples", "bananas", "cherries"];
+///
+/// let _: AnyComponent = render! {
+///   VStack() {
+///     Section(title: "Header")
+///
+///     ..items.into_iter().map(|item| render! { Text(text: item) })
+///   }
+/// };
+/// ```
+/// renders a `Section`, followed by one `Text` for each item. The `flex` of stacks can
+/// similarly be given a `Vec`, as described in [`components::stack::FlexList`].
+///
+/// ## Control Flow
+/// Children can also be rendered conditionally or repeatedly with `if`, `match`, and
+/// `for`, which work like their Rust counterparts, except that their bodies contain
+/// children instead of expressions:
+/// ```rust
+/// # use intuitive::{components::{Any as AnyComponent, Empty, Section, Text, VStack}, render};
+/// #
+/// let items = vec!["apples", "bananas", "cherries"];
+/// let selected = Some(1);
+///
+/// let _: AnyComponent = render! {
+///   VStack() {
+///     if items.is_empty() {
+///       Text(text: "nothing here")
+///     } else {
+///       Section(title: "Items")
+///     }
+///
+///     for &item in &items {
+///       Text(text: item)
+///     }
+///
+///     match selected {
+///       Some(idx) => Text(text: items[idx]),
+///       None => Empty(),
+///     }
+///   }
+/// };
+/// ```
+///
+/// Since the number of children is then only known at runtime, this is only supported for
+/// components taking a [`children::List`].
+///
+/// Normally, hooks must be called in the same order on every render, which would not hold
+/// if a branch or iteration is added or removed. Instead, the children of each branch and
+/// iteration are wrapped in a [`components::Keyed`], storing their hooks by the location of
+/// the construct, the branch or index of the iteration, and the position of the child. When
+/// a branch stops being rendered, the state of its children is dropped.
+///
+/// Since items in a `for` loop are keyed by their index, removing or reordering items moves
+/// state from one item to another. Any component can instead be given a `key` parameter,
+/// which is passed to a [`components::Keyed`] wrapping it instead of the component itself:
+/// ```rust
+/// # use intuitive::{components::{Any as AnyComponent, Text, VStack}, render};
+/// #
+/// # let items = vec!["apples", "bananas", "cherries"];
+/// let _: AnyComponent = render! {
+///   VStack() {
+///     for &item in &items {
+///       Text(key: item, text: item)
+///     }
+///   }
+/// };
+/// ```
 ///
+/// [`children::List`]: components/children/struct.List.html
+/// [`components::Any`]: components/struct.Any.html
+/// [`components::Keyed`]: components/struct.Keyed.html
+/// [`components::stack::FlexList`]: components/stack/struct.FlexList.html
 /// [`From`]: https://doc.rust-lang.org/std/convert/trait.From.html
+/// [`Into::into`]: https://doc.rust-lang.org/std/convert/trait.Into.html#tymethod.into
+/// [`param`]: components/param/index.html
 /// [`Spans`]: spans/struct.Spans.html
 /// [`Style`]: style/struct.Style.html
 /// [`TryInto::try_into`]: https://doc.rust-lang.org/std/convert/trait.TryInto.html#tymethod.try_into
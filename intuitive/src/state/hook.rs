@@ -1,13 +1,134 @@
-use parking_lot::Mutex;
+use std::{panic::Location, sync::Arc};
+
+use parking_lot::{Mutex, MutexGuard};
 
 use super::manager::Manager;
-pub use super::State;
-use crate::error::Error;
+use super::{receiver::Receiving, task::Task};
+pub use super::{Ref, State, TaskState};
+use crate::error::Result;
 
 static MANAGER: Mutex<Manager> = Mutex::new(Manager::new());
 
-pub fn render_done() {
-  MANAGER.lock().reset().map_err(|err| Error::UseState(err.to_string())).unwrap();
+/// Panics on hook-order violations in debug builds, so that they are reported where they occur.
+fn check(manager: &MutexGuard<Manager>) {
+  if cfg!(debug_assertions) {
+    if let Some(err) = manager.error() {
+      panic!("{}", err);
+    }
+  }
+}
+
+/// Returns the value stored by the manager for the hook being called, or `initializer`
+/// if the hooks were not called in the same order as in the previous render.
+#[track_caller]
+fn next<S, F>(initializer: F) -> std::result::Result<S, F>
+where
+  S: 'static + Clone + Send + Sync,
+  F: FnOnce() -> S,
+{
+  let location = Location::caller();
+
+  let mut manager = MANAGER.lock();
+  let value = manager.next(location, initializer);
+  check(&manager);
+
+  value
+}
+
+pub fn render_done() -> Result<()> {
+  let result = MANAGER.lock().reset();
+
+  if cfg!(debug_assertions) {
+    if let Err(err) = result {
+      panic!("{}", err);
+    }
+  }
+
+  result
+}
+
+/// Drops the values of all hooks, so that tests start from an empty manager.
+#[cfg(test)]
+pub(crate) fn reset() {
+  *MANAGER.lock() = Manager::new();
+}
+
+/// A guard returned by [`component_scope`].
+///
+/// [`component_scope`]: fn.component_scope.html
+pub struct ComponentScope {
+  _private: (),
+}
+
+impl Drop for ComponentScope {
+  fn drop(&mut self) {
+    MANAGER.lock().exit();
+  }
+}
+
+/// Attributes any hooks called until the returned guard is dropped to the component `name`.
+///
+/// This is used to describe which component violated the hook ordering rules, when
+/// they are violated. The [`component` attribute macro] calls this automatically, so it
+/// is only needed when implementing [`Component`] directly:
+/// ```rust
+/// # use intuitive::{components::{Component, Empty}, element::Any as AnyElement, render, state::{component_scope, use_state}};
+/// #
+/// #[derive(Default)]
+/// struct Counter {}
+///
+/// impl Component for Counter {
+///   fn render(&self) -> AnyElement {
+///     let _scope = component_scope("Counter");
+///     let count = use_state(|| 0);
+///
+///     render! {
+///       Empty()
+///     }
+///   }
+/// }
+/// ```
+///
+/// [`component` attribute macro]: ../attr.component.html
+/// [`Component`]: ../components/trait.Component.html
+pub fn component_scope(name: &'static str) -> ComponentScope {
+  MANAGER.lock().enter(name);
+
+  ComponentScope { _private: () }
+}
+
+/// A guard returned by [`key_scope`].
+///
+/// [`key_scope`]: fn.key_scope.html
+pub(crate) struct KeyScope {
+  _private: (),
+}
+
+impl Drop for KeyScope {
+  fn drop(&mut self) {
+    let mut manager = MANAGER.lock();
+    manager.exit_key();
+    check(&manager);
+  }
+}
+
+/// Stores the values of any hooks called until the returned guard is dropped by `key`,
+/// rather than by the order in which they are called. This is used by [`Keyed`].
+///
+/// [`Keyed`]: ../components/struct.Keyed.html
+pub(crate) fn key_scope(key: u64) -> KeyScope {
+  MANAGER.lock().enter_key(key);
+
+  KeyScope { _private: () }
+}
+
+/// Keeps the values of the hooks stored by `key`, as if [`key_scope`] was called and the
+/// same hooks were called as before. This is used by [`use_memo`].
+///
+/// [`key_scope`]: fn.key_scope.html
+/// [`use_memo`]: fn.use_memo.html
+pub(crate) fn retain_key(key: u64) {
+  MANAGER.lock().retain_key(key);
 }
 
 /// A hook for managing state within a [`Component`]
@@ -38,25 +159,205 @@ pub fn render_done() {
 /// # Gotchas
 /// Any calls to `use_state` must always be called in the same order and in every render.
 /// This means that there can not be any conditional logic around the calling of `use_state`.
-/// If Intuitive detects such a violation, it will panic with an appropriate message.
+/// If Intuitive detects such a violation, it describes which component called a different
+/// number of hooks than in the previous render, and where its first hook is called. In debug
+/// builds this is a panic, while in release builds [`Terminal::run`] returns an
+/// [`Error::HookOrder`].
+///
+/// Components rendered conditionally with the `if`, `match`, and `for` constructs of
+/// [`render!`] are exempt from this, as their hooks are stored by key, as described in
+/// [`Keyed`].
 ///
 /// [`Component`]: ../components/trait.Component.html
+/// [`Error::HookOrder`]: ../error/enum.Error.html#variant.HookOrder
+/// [`Keyed`]: ../components/struct.Keyed.html
+/// [`render!`]: ../macro.render.html
 /// [`State`]: struct.State.html
+/// [`Terminal::run`]: ../terminal/struct.Terminal.html#method.run
 /// [React Hooks]: https://reactjs.org/docs/hooks-intro.html
+#[track_caller]
 pub fn use_state<T, F>(initializer: F) -> State<T>
 where
   T: 'static + Send,
   F: FnOnce() -> T,
 {
-  MANAGER
-    .lock()
-    .next(initializer)
-    .map_err(|err| Error::UseState(err.to_string()))
-    .unwrap()
+  next(|| State::new(initializer())).unwrap_or_else(|initializer| initializer())
+}
+
+/// A hook for storing a value across renders without triggering re-renders.
+///
+/// `use_ref` returns a [`Ref`], which is like a [`State`], except that writing to
+/// it never triggers a re-render. This is useful for values that are not displayed,
+/// such as cached computations, timestamps, or handles to child processes. For example,
+/// ```rust
+/// # use std::time::Instant;
+/// # use intuitive::{component, components::Text, state::{use_ref, use_state}, on_key, render};
+/// #
+/// #[component(Root)]
+/// fn render() {
+///   let count = use_state(|| 0);
+///   let last_press = use_ref(|| None);
+///
+///   let on_key = on_key! { [count, last_press]
+///     KeyEvent { code: Enter, .. } => {
+///       last_press.set(Some(Instant::now()));
+///       count.update(|count| count + 1);
+///     },
+///   };
+///
+///   render! {
+///     Text(text: format!("pressed {} times", count.get()), on_key)
+///   }
+/// }
+/// ```
+///
+/// Calls to `use_ref` follow the same rules as calls to [`use_state`].
+///
+/// [`Ref`]: struct.Ref.html
+/// [`State`]: struct.State.html
+/// [`use_state`]: fn.use_state.html
+#[track_caller]
+pub fn use_ref<T, F>(initializer: F) -> Ref<T>
+where
+  T: 'static + Send,
+  F: FnOnce() -> T,
+{
+  next(|| Ref::new(initializer())).unwrap_or_else(|initializer| initializer())
+}
+
+/// A hook for running work on a background thread, and rendering its result.
+///
+/// `use_task` runs `work` on a new thread the first time it is called, and then again
+/// whenever `deps` differ from the `deps` of the previous call. It returns the
+/// [`TaskState`] of the most recently started task, and triggers a re-render once that
+/// task finishes. For example,
+/// ```rust
+/// # use std::{fs, io};
+/// # use intuitive::{component, components::Text, state::{use_task, TaskState}, render};
+/// #
+/// #[component(FileContents)]
+/// fn render(path: String) {
+///   let path = path.clone();
+///   let contents = use_task(path.clone(), move || fs::read_to_string(path).map_err(|err| err.to_string()));
+///
+///   let text = match contents {
+///     TaskState::Pending => String::from("loading..."),
+///     TaskState::Ready(contents) => contents,
+///     TaskState::Failed(err) => format!("error: {}", err),
+///   };
+///
+///   render! {
+///     Text(text)
+///   }
+/// }
+/// ```
+///
+/// # Cancellation
+/// A task is cancelled when a newer task is started because `deps` changed, or when the
+/// values of the hook are dropped. Hook values are only dropped when the hook is called
+/// within a [`Keyed`] component, or a child of an `if`, `match`, or `for` in a
+/// [`render!`] macro, that is no longer rendered. Otherwise, they are kept for as long as
+/// the application runs. The thread running a cancelled task is not stopped, but its
+/// result is discarded and does not trigger a re-render.
+///
+/// Calls to `use_task` follow the same rules as calls to [`use_state`].
+///
+/// [`Keyed`]: ../components/struct.Keyed.html
+/// [`render!`]: ../macro.render.html
+/// [`TaskState`]: enum.TaskState.html
+/// [`use_state`]: fn.use_state.html
+#[track_caller]
+pub fn use_task<D, T, E, F>(deps: D, work: F) -> TaskState<T, E>
+where
+  D: 'static + PartialEq + Send,
+  T: 'static + Clone + Send,
+  E: 'static + Clone + Send,
+  F: 'static + FnOnce() -> std::result::Result<T, E> + Send,
+{
+  // a task is not started while the hooks are out of order, since it would not be stored
+  let Ok(task) = next(|| Arc::new(Task::<D, T, E>::new())) else {
+    return TaskState::Pending;
+  };
+  task.run(deps, work);
+
+  task.status().get()
+}
+
+/// A hook for reducing the items of a channel or iterator into a value.
+///
+/// `use_receiver` calls `source` the first time it is called, and forwards the items of
+/// the returned iterator from a background thread. Each time the hook is called, any
+/// items received since the last call are folded into the returned value using
+/// `reducer`, starting from `S::default()`. A re-render is triggered when items arrive,
+/// and bursts of items are coalesced into a single render.
+///
+/// Because [`std::sync::mpsc::Receiver`] (and most other channel receivers) implement
+/// [`IntoIterator`], a channel can be used directly as the `source`, instead of having
+/// producers hold a [`State`] and call [`State::set`]. For example,
+/// ```rust
+/// # use std::{sync::mpsc, thread, time::Duration};
+/// # use intuitive::{component, components::Text, state::use_receiver, render};
+/// #
+/// #[component(Ticks)]
+/// fn render() {
+///   let ticks = use_receiver(
+///     || {
+///       let (sender, receiver) = mpsc::channel();
+///
+///       thread::spawn(move || loop {
+///         thread::sleep(Duration::from_secs(1));
+///
+///         if sender.send(()).is_err() {
+///           break;
+///         }
+///       });
+///
+///       receiver
+///     },
+///     |ticks: &mut u64, ()| *ticks += 1,
+///   );
+///
+///   render! {
+///     Text(text: format!("{} ticks", ticks))
+///   }
+/// }
+/// ```
+///
+/// Once the values of the hook are dropped, the forwarding thread stops after receiving
+/// its next item. As with [`use_task`], this only happens when the hook is called within
+/// a [`Keyed`] component, or a child of an `if`, `match`, or `for` in a [`render!`]
+/// macro, that is no longer rendered. Calls to `use_receiver` follow the same rules as
+/// calls to [`use_state`].
+///
+/// [`IntoIterator`]: https://doc.rust-lang.org/std/iter/trait.IntoIterator.html
+/// [`State`]: struct.State.html
+/// [`State::set`]: struct.State.html#method.set
+/// [`Keyed`]: ../components/struct.Keyed.html
+/// [`render!`]: ../macro.render.html
+/// [`std::sync::mpsc::Receiver`]: https://doc.rust-lang.org/std/sync/mpsc/struct.Receiver.html
+/// [`use_state`]: fn.use_state.html
+/// [`use_task`]: fn.use_task.html
+#[track_caller]
+pub fn use_receiver<I, F, S, R>(source: F, reducer: R) -> S
+where
+  I: IntoIterator,
+  I::Item: 'static + Send,
+  I::IntoIter: 'static + Send,
+  F: FnOnce() -> I,
+  S: 'static + Clone + Default + Send,
+  R: Fn(&mut S, I::Item),
+{
+  let Ok(receiving) = next(|| Arc::new(Receiving::new(source().into_iter(), S::default()))) else {
+    return S::default();
+  };
+
+  receiving.drain(reducer)
 }
 
 #[cfg(test)]
 mod tests {
+  use std::{sync::mpsc, thread, time::Duration};
+
   use serial_test::serial;
 
   use super::*;
@@ -67,7 +368,7 @@ mod tests {
     let _ = use_state(|| 1);
     let _ = use_state(|| 2);
 
-    render_done();
+    render_done().unwrap();
   }
 
   #[test]
@@ -78,7 +379,7 @@ mod tests {
     let _ = use_state(|| 1);
     let _ = use_state(|| 2);
 
-    render_done();
+    render_done().unwrap();
   }
 
   #[test]
@@ -92,7 +393,7 @@ mod tests {
     assert_eq!(state_1.get(), 1);
     assert_eq!(state_2.get(), 2);
 
-    render_done();
+    render_done().unwrap();
   }
 
   #[test]
@@ -109,7 +410,7 @@ mod tests {
     assert_eq!(state_1.get(), 3);
     assert_eq!(state_2.get(), 4);
 
-    render_done();
+    render_done().unwrap();
   }
 
   #[test]
@@ -120,7 +421,7 @@ mod tests {
 
     let _ = use_state(|| ());
 
-    render_done();
+    render_done().unwrap();
   }
 
   #[test]
@@ -131,7 +432,7 @@ mod tests {
 
     let _ = use_state(|| 1);
 
-    render_done();
+    render_done().unwrap();
   }
 
   #[test]
@@ -144,6 +445,212 @@ mod tests {
     let _ = use_state(|| 2);
     let _ = use_state(|| 3);
 
-    render_done();
+    render_done().unwrap();
+  }
+
+  #[test]
+  #[serial]
+  #[should_panic(expected = "component `Foo` (at `Root > Foo`) called 1 hooks, previously 2, first at")]
+  fn use_state_too_few_in_component() {
+    *MANAGER.lock() = Manager::new();
+
+    for calls in [2, 1] {
+      let _root = component_scope("Root");
+      let _ = use_state(|| 0);
+
+      {
+        let _foo = component_scope("Foo");
+        for _ in 0..calls {
+          let _ = use_state(|| 0);
+        }
+      }
+
+      drop(_root);
+      render_done().unwrap();
+    }
+  }
+
+  #[test]
+  #[serial]
+  fn use_ref_persists() {
+    *MANAGER.lock() = Manager::new();
+
+    let count = use_ref(|| 0);
+    count.set(1);
+    render_done().unwrap();
+
+    let count = use_ref(|| 0);
+    count.mutate(|count| *count += 1);
+    render_done().unwrap();
+
+    assert_eq!(use_ref(|| 0).get(), 2);
+    render_done().unwrap();
+  }
+
+  #[test]
+  #[serial]
+  fn key_scopes_store_hooks_by_key() {
+    *MANAGER.lock() = Manager::new();
+
+    let render = |keys: &[u64]| {
+      let _ = use_state(|| 0);
+
+      let counts = keys
+        .iter()
+        .map(|&key| {
+          let _scope = key_scope(key);
+          let count = use_ref(|| 0);
+          count.mutate(|count| *count += 1);
+
+          // scopes may call a different number of hooks than their siblings
+          if key == 2 {
+            let _ = use_state(|| ());
+          }
+
+          count.get()
+        })
+        .collect::<Vec<_>>();
+
+      render_done().unwrap();
+      counts
+    };
+
+    assert_eq!(render(&[1, 2]), [1, 1]);
+    assert_eq!(render(&[2, 1]), [2, 2]);
+    assert_eq!(render(&[1, 1]), [3, 1]);
+
+    // the scope with key 2 was not entered in the last render, so it starts over
+    assert_eq!(render(&[2, 1]), [1, 4]);
+  }
+
+  fn wait_for<T, E>(task: impl Fn() -> TaskState<T, E>) -> TaskState<T, E> {
+    for _ in 0..100 {
+      let status = task();
+      if !status.is_pending() {
+        return status;
+      }
+
+      thread::sleep(Duration::from_millis(10));
+    }
+
+    panic!("task did not finish");
+  }
+
+  #[test]
+  #[serial]
+  fn use_task_restarts_on_deps() {
+    *MANAGER.lock() = Manager::new();
+
+    let render = |deps: i32| {
+      let status = use_task(deps, move || if deps >= 0 { Ok(deps * 2) } else { Err("negative") });
+      render_done().unwrap();
+
+      status
+    };
+
+    assert_eq!(render(1), TaskState::Pending);
+    assert_eq!(wait_for(|| render(1)), TaskState::Ready(2));
+
+    assert_eq!(render(-1), TaskState::Pending);
+    assert_eq!(wait_for(|| render(-1)), TaskState::Failed("negative"));
+  }
+
+  #[test]
+  #[serial]
+  fn use_task_discards_cancelled() {
+    *MANAGER.lock() = Manager::new();
+
+    let (sender, receiver) = mpsc::channel::<()>();
+
+    let status = use_task(1, move || receiver.recv().map(|()| 1).map_err(|_| ()));
+    render_done().unwrap();
+    assert!(status.is_pending());
+
+    // changing the deps cancels the first task, which is still waiting on the channel
+    let status = use_task(2, || Ok::<_, ()>(2));
+    render_done().unwrap();
+    assert!(status.is_pending());
+
+    assert_eq!(
+      wait_for(|| {
+        let status = use_task(2, || Ok::<_, ()>(0));
+        render_done().unwrap();
+
+        status
+      }),
+      TaskState::Ready(2)
+    );
+
+    sender.send(()).unwrap();
+    thread::sleep(Duration::from_millis(50));
+
+    assert_eq!(use_task(2, || Ok::<_, ()>(0)), TaskState::Ready(2));
+    render_done().unwrap();
+  }
+
+  #[test]
+  #[serial]
+  fn use_receiver_reduces_items() {
+    *MANAGER.lock() = Manager::new();
+
+    let (sender, receiver) = mpsc::channel();
+    let receiver = Mutex::new(Some(receiver));
+
+    let render = || {
+      let items = use_receiver(|| receiver.lock().take().unwrap(), |items: &mut Vec<i32>, item| items.push(item));
+      render_done().unwrap();
+
+      items
+    };
+
+    assert_eq!(render(), Vec::<i32>::new());
+
+    for item in 0..3 {
+      sender.send(item).unwrap();
+    }
+
+    for _ in 0..100 {
+      if render().len() == 3 {
+        break;
+      }
+
+      thread::sleep(Duration::from_millis(10));
+    }
+
+    assert_eq!(render(), vec![0, 1, 2]);
+  }
+
+  #[test]
+  fn manager_reports_error() {
+    let mut manager = Manager::new();
+    let location = Location::caller();
+
+    for calls in [2, 3] {
+      manager.enter("Foo");
+      for _ in 0..calls {
+        let _ = manager.next(location, || State::new(0));
+      }
+      manager.exit();
+
+      if calls == 2 {
+        manager.reset().unwrap();
+      }
+    }
+
+    // initializers are not called once the hooks are out of order
+    manager.enter("Foo");
+    assert!(manager.next(location, || -> State<i32> { unreachable!() }).is_err());
+    manager.exit();
+
+    let err = manager.reset().unwrap_err().to_string();
+    assert!(err.contains("component `Foo` (at `Foo`) called 3 hooks, previously 2"), "{}", err);
+
+    // the manager keeps working after an error is reported
+    manager.enter("Foo");
+    for _ in 0..2 {
+      let _ = manager.next(location, || State::new(0));
+    }
+    manager.exit();
+    manager.reset().unwrap();
   }
 }
@@ -1,51 +1,396 @@
-use std::any::Any;
+use std::{
+  any::{self, Any},
+  collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet},
+  hash::{Hash, Hasher},
+  mem,
+  panic::Location,
+};
 
-use super::State;
 use crate::error::{Error, Result};
 
-pub struct Manager {
+/// A component that was entered while rendering, through [`Manager::enter`].
+struct Instance {
+  name: &'static str,
+  parent: Option<usize>,
+  /// How many instances were entered with the same parent before this one.
+  ordinal: usize,
+  children: usize,
+}
+
+/// A single call to a hook.
+struct Call {
+  scope: u64,
+  instance: Option<usize>,
+  location: &'static Location<'static>,
+  ty: &'static str,
+}
+
+/// The scope of the hooks called outside of any keyed scope.
+const ROOT: u64 = 0;
+
+/// A record of the components entered and hooks called during a single render.
+#[derive(Default)]
+struct Render {
+  instances: Vec<Instance>,
+  roots: usize,
+  calls: Vec<Call>,
+}
+
+/// Identifies a component instance across renders, as its path from the root
+/// component along with how many times that path was previously entered.
+type Key = (Vec<&'static str>, usize);
+
+impl Render {
+  const fn new() -> Self {
+    Self {
+      instances: Vec::new(),
+      roots: 0,
+      calls: Vec::new(),
+    }
+  }
+
+  fn path(&self, mut instance: Option<usize>) -> Vec<&'static str> {
+    let mut path = Vec::new();
+    while let Some(idx) = instance {
+      path.push(self.instances[idx].name);
+      instance = self.instances[idx].parent;
+    }
+
+    path.reverse();
+    path
+  }
+
+  fn key(&self, instance: Option<usize>) -> Option<Key> {
+    let idx = instance?;
+    let path = self.path(instance);
+    let occurrence = (0..idx).filter(|&other| self.path(Some(other)) == path).count();
+
+    Some((path, occurrence))
+  }
+
+  fn calls_in(&self, scope: u64) -> Vec<&Call> {
+    self.calls.iter().filter(|call| call.scope == scope).collect()
+  }
+
+  fn calls_by(&self, scope: u64, key: &Option<Key>) -> Vec<&Call> {
+    self
+      .calls_in(scope)
+      .into_iter()
+      .filter(|call| &self.key(call.instance) == key)
+      .collect()
+  }
+}
+
+fn describe(key: &Option<Key>) -> String {
+  match key {
+    Some((path, _)) => format!(
+      "component `{}` (at `{}`)",
+      path.last().expect("empty component path"),
+      path.join(" > ")
+    ),
+    None => String::from("code outside of any component"),
+  }
+}
+
+/// Describes how the hook calls in `scope` in `current` diverged from those in `previous`.
+fn diagnose(current: &Render, previous: &Render, scope: u64) -> String {
+  let (current_calls, previous_calls) = (current.calls_in(scope), previous.calls_in(scope));
+
+  let differs = |idx: usize| match (current_calls.get(idx), previous_calls.get(idx)) {
+    (Some(cur), Some(prev)) => {
+      cur.location != prev.location || cur.ty != prev.ty || current.key(cur.instance) != previous.key(prev.instance)
+    }
+    _ => true,
+  };
+
+  let idx = (0..).find(|&idx| differs(idx)).expect("unbounded range");
+
+  let (cur, prev) = (current_calls.get(idx).copied(), previous_calls.get(idx).copied());
+  let cur_key = cur.and_then(|call| current.key(call.instance));
+  let prev_key = prev.and_then(|call| previous.key(call.instance));
+
+  if let (Some(cur), Some(prev)) = (cur, prev) {
+    if cur_key == prev_key {
+      return format!(
+        "{} called a hook of type `{}` at {}, previously `{}` at {}",
+        describe(&cur_key),
+        cur.ty,
+        cur.location,
+        prev.ty,
+        prev.location
+      );
+    }
+  }
+
+  // blame whichever of the two components called a different number of hooks than before
+  let candidates = [prev.map(|_| prev_key), cur.map(|_| cur_key)];
+  for key in candidates.into_iter().flatten() {
+    let (cur_calls, prev_calls) = (current.calls_by(scope, &key), previous.calls_by(scope, &key));
+
+    if cur_calls.len() != prev_calls.len() {
+      let first = prev_calls.first().or_else(|| cur_calls.first()).expect("no hook calls");
+
+      return format!(
+        "{} called {} hooks, previously {}, first at {}",
+        describe(&key),
+        cur_calls.len(),
+        prev_calls.len(),
+        first.location
+      );
+    }
+  }
+
+  let location = cur.or(prev).expect("no hook calls").location;
+
+  format!("hooks were called in a different order than in the previous render, starting at {location}")
+}
+
+/// The hook values of the root of a render, or of a keyed scope.
+struct Slot {
   states: Vec<Box<dyn Any + Send + Sync>>,
   idx: usize,
   filling: bool,
+  parent: u64,
 }
 
-impl Manager {
-  pub const fn new() -> Self {
+impl Slot {
+  const fn new(parent: u64) -> Self {
     Self {
       states: Vec::new(),
       idx: 0,
       filling: true,
+      parent,
     }
   }
+}
+
+/// Stores the values of hooks across renders.
+///
+/// The first render "fills" the manager, and every subsequent render must call the
+/// same hooks in the same order. The components being rendered and the location of each
+/// hook call are recorded, in order to describe any violations of this.
+///
+/// Hooks called within a keyed scope, entered through [`Manager::enter_key`], are instead
+/// stored by the key of the scope, and must only be called in the same order as in the
+/// last render in which the scope was entered. The values of scopes that are not entered
+/// during a render are dropped.
+pub struct Manager {
+  root: Slot,
+  slots: BTreeMap<u64, Slot>,
+  scopes: Vec<(u64, usize)>,
+  entered: BTreeMap<u64, usize>,
+  visited: BTreeSet<u64>,
 
-  pub fn next<T, F>(&mut self, initializer: F) -> Result<State<T>>
+  stack: Vec<usize>,
+  current: Render,
+  previous: Render,
+  error: Option<Error>,
+}
+
+impl Manager {
+  pub const fn new() -> Self {
+    Self {
+      root: Slot::new(ROOT),
+      slots: BTreeMap::new(),
+      scopes: Vec::new(),
+      entered: BTreeMap::new(),
+      visited: BTreeSet::new(),
+
+      stack: Vec::new(),
+      current: Render::new(),
+      previous: Render::new(),
+      error: None,
+    }
+  }
+
+  fn scope(&self) -> u64 {
+    self.scopes.last().map_or(ROOT, |(scope, _)| *scope)
+  }
+
+  fn slot(&mut self) -> &mut Slot {
+    match self.scopes.last() {
+      Some((scope, _)) => self.slots.get_mut(scope).expect("missing keyed scope"),
+      None => &mut self.root,
+    }
+  }
+
+  /// Records an error if the hooks of the current scope were not all called.
+  fn check_done(&mut self) {
+    let slot = self.slot();
+
+    if !slot.filling && slot.idx != slot.states.len() && self.error.is_none() {
+      self.error = Some(Error::HookOrder(diagnose(&self.current, &self.previous, self.scope())));
+    }
+  }
+
+  /// Marks the start of the rendering of the component `name`.
+  pub fn enter(&mut self, name: &'static str) {
+    let parent = self.stack.last().copied();
+    let siblings = match parent {
+      Some(parent) => &mut self.current.instances[parent].children,
+      None => &mut self.current.roots,
+    };
+    let ordinal = mem::replace(siblings, *siblings + 1);
+
+    self.current.instances.push(Instance {
+      name,
+      parent,
+      ordinal,
+      children: 0,
+    });
+    self.stack.push(self.current.instances.len() - 1);
+  }
+
+  /// Marks the end of the rendering of the most recently entered component.
+  pub fn exit(&mut self) {
+    self.stack.pop();
+  }
+
+  /// Returns the scope with the key `key` in the current scope, marking it as visited.
+  ///
+  /// The scope is identified by the enclosing scope, the position of the component calling
+  /// this within the enclosing scope, and `key`, so that components rendered in the same
+  /// scope do not share their keyed scopes. The position is that of the parent of the
+  /// current component, as the current component, such as a `Keyed`, is itself positioned
+  /// by `key`.
+  fn visit_key(&mut self, key: u64) -> u64 {
+    let parent = self.scope();
+    let depth = self.scopes.last().map_or(0, |(_, depth)| *depth);
+
+    let position: Vec<(&'static str, usize)> = self.stack[depth.min(self.stack.len())..]
+      .iter()
+      .rev()
+      .skip(1)
+      .map(|&idx| (self.current.instances[idx].name, self.current.instances[idx].ordinal))
+      .collect();
+
+    let mut hasher = DefaultHasher::new();
+    (parent, position, key).hash(&mut hasher);
+    let base = hasher.finish();
+
+    let entered = self.entered.entry(base).or_default();
+    let mut hasher = DefaultHasher::new();
+    (base, *entered).hash(&mut hasher);
+    *entered += 1;
+
+    // the root scope is never keyed
+    let scope = hasher.finish().max(ROOT + 1);
+    self.visited.insert(scope);
+
+    scope
+  }
+
+  /// Marks the start of a keyed scope, whose hooks are stored by `key`.
+  ///
+  /// Keys only need to be unique among the scopes entered by the same component within
+  /// their enclosing scope. Scopes with the same key are told apart by the order in which
+  /// they are entered.
+  pub fn enter_key(&mut self, key: u64) {
+    let parent = self.scope();
+    let scope = self.visit_key(key);
+
+    self.slots.entry(scope).or_insert_with(|| Slot::new(parent)).idx = 0;
+    self.scopes.push((scope, self.stack.len()));
+  }
+
+  /// Keeps the values of the keyed scope `key`, and of all scopes nested within it, as if
+  /// it was entered and its hooks were called in the same order as before.
+  pub fn retain_key(&mut self, key: u64) {
+    let mut retained = vec![self.visit_key(key)];
+
+    while let Some(scope) = retained.pop() {
+      let children = self.slots.iter().filter(|(_, slot)| slot.parent == scope).map(|(child, _)| *child);
+      let children: Vec<u64> = children.filter(|child| !self.visited.contains(child)).collect();
+
+      self.visited.extend(children.iter().copied());
+      retained.extend(children);
+    }
+  }
+
+  /// Marks the end of the most recently entered keyed scope.
+  pub fn exit_key(&mut self) {
+    self.check_done();
+
+    let slot = self.slot();
+    slot.filling = false;
+    slot.idx = 0;
+
+    self.scopes.pop();
+  }
+
+  /// Returns the hook value for the current call, creating it with `initializer` if
+  /// this is the first render.
+  ///
+  /// If the call is not consistent with the previous render, or an error was already
+  /// recorded during this render, the error is recorded and `initializer` is returned
+  /// without being called, so that hooks can return a value that is not stored across
+  /// renders without starting any work. The error can be retrieved through
+  /// [`Manager::error`], and is also returned from [`Manager::reset`].
+  pub fn next<S, F>(&mut self, location: &'static Location<'static>, initializer: F) -> std::result::Result<S, F>
   where
-    T: 'static + Send,
-    F: FnOnce() -> T,
+    S: 'static + Clone + Send + Sync,
+    F: FnOnce() -> S,
   {
-    if self.filling {
-      let state = State::new(initializer());
-      self.states.push(Box::new(state.clone()));
+    self.current.calls.push(Call {
+      scope: self.scope(),
+      instance: self.stack.last().copied(),
+      location,
+      ty: any::type_name::<S>(),
+    });
 
-      Ok(state)
-    } else {
-      let state = self.states.get(self.idx).ok_or(Error::Manager("invalid index"))?;
+    if self.error.is_some() {
+      return Err(initializer);
+    }
+
+    let slot = self.slot();
+
+    if slot.filling {
+      let state = initializer();
+      slot.states.push(Box::new(state.clone()));
 
-      self.idx += 1;
+      return Ok(state);
+    }
+
+    let state = slot.states.get(slot.idx).and_then(|state| state.downcast_ref::<S>()).cloned();
+    slot.idx += 1;
 
-      Ok(state.downcast_ref::<State<T>>().ok_or(Error::Manager("invalid type"))?.clone())
+    match state {
+      Some(state) => Ok(state),
+      None => {
+        self.error = Some(Error::HookOrder(diagnose(&self.current, &self.previous, self.scope())));
+        Err(initializer)
+      }
     }
   }
 
+  /// Returns the error recorded during the current render, if any.
+  pub fn error(&self) -> Option<&Error> {
+    self.error.as_ref()
+  }
+
+  /// Marks the end of a render, checking that all hooks from the previous render were
+  /// called, and dropping the values of keyed scopes that were not entered.
   pub fn reset(&mut self) -> Result<()> {
-    if self.filling {
-      self.filling = false;
-    } else if self.idx != self.states.len() {
-      return Err(Error::Manager("insufficient calls"));
-    }
+    self.scopes.clear();
+    self.check_done();
+
+    self.root.filling = false;
+    self.root.idx = 0;
+    self.stack.clear();
 
-    self.idx = 0;
+    let visited = mem::take(&mut self.visited);
+    self.slots.retain(|scope, _| visited.contains(scope));
+    self.entered.clear();
 
-    Ok(())
+    let current = mem::take(&mut self.current);
+
+    match self.error.take() {
+      Some(err) => Err(err),
+      None => {
+        self.previous = current;
+
+        Ok(())
+      }
+    }
   }
 }
@@ -0,0 +1,250 @@
+use std::sync::{
+  atomic::{AtomicBool, Ordering},
+  Arc, Weak,
+};
+
+use parking_lot::Mutex;
+
+use super::hook::{key_scope, retain_key, use_ref};
+use crate::{element::Any as AnyElement, focus};
+
+/// The recordings of the memoized components currently being rendered, innermost last.
+static RECORDINGS: Mutex<Vec<Recording>> = Mutex::new(Vec::new());
+
+/// The key of the scope storing the hooks of a memoized component's render.
+const MEMO_KEY: u64 = 0;
+
+type Effect = Arc<dyn Fn() + 'static + Send + Sync>;
+
+/// What happened while rendering a memoized component, so that it can be repeated when
+/// the rendered element is reused.
+#[derive(Clone, Default)]
+struct Recording {
+  /// Set once any of the values read while rendering changes.
+  dirty: Arc<AtomicBool>,
+  /// The side effects of rendering, such as registering focusable components.
+  effects: Vec<Effect>,
+  /// The values read while rendering.
+  dependents: Vec<Dependents>,
+}
+
+/// The memoized components depending on a value, which are re-rendered once it changes.
+#[derive(Clone, Default)]
+pub(crate) struct Dependents(Arc<Mutex<Vec<Weak<AtomicBool>>>>);
+
+impl Dependents {
+  /// Marks the memoized components currently being rendered as depending on the value.
+  pub fn track(&self) {
+    let mut recordings = RECORDINGS.lock();
+    if recordings.is_empty() {
+      return;
+    }
+
+    let mut flags = self.0.lock();
+    flags.retain(|flag| flag.strong_count() > 0);
+
+    for recording in recordings.iter_mut() {
+      if !flags.iter().any(|flag| flag.as_ptr() == Arc::as_ptr(&recording.dirty)) {
+        flags.push(Arc::downgrade(&recording.dirty));
+        recording.dependents.push(self.clone());
+      }
+    }
+  }
+
+  /// Marks the memoized components depending on the value as changed.
+  pub fn invalidate(&self) {
+    for flag in self.0.lock().drain(..) {
+      if let Some(flag) = flag.upgrade() {
+        flag.store(true, Ordering::SeqCst);
+      }
+    }
+  }
+}
+
+/// Performs a side effect of rendering, which is repeated whenever an element of a
+/// memoized component that performed it is reused.
+pub(crate) fn effect<F>(effect: F)
+where
+  F: Fn() + 'static + Send + Sync,
+{
+  replay(Arc::new(effect));
+}
+
+fn replay(effect: Effect) {
+  effect();
+
+  for recording in RECORDINGS.lock().iter_mut() {
+    recording.effects.push(effect.clone());
+  }
+}
+
+/// A cached render of a memoized component.
+struct Cache<P> {
+  props: P,
+  focus: focus::Fingerprint,
+  element: AnyElement,
+  recording: Recording,
+}
+
+/// A hook for skipping the render of a component when it is unchanged.
+///
+/// `use_memo` calls `render` and caches the returned element. On subsequent renders,
+/// `render` is skipped and the cached element is returned, unless `props` differ from
+/// those of the cached render, or anything read while rendering changed since. Rendering
+/// reads:
+/// - any [`State`] or [`Derived`] through [`State::get`] or [`State::inspect`],
+/// - the results of [`use_task`] and [`use_receiver`],
+/// - and the focus, through [`use_focus`].
+///
+/// Values that are neither props nor read through these, such as a [`Ref`] or a global
+/// variable, do not cause a re-render once they change. The [`component` attribute macro]
+/// memoizes a component with `#[component(Name, memo)]`, which is usually preferable to
+/// calling `use_memo` directly.
+///
+/// # Hooks
+/// The hooks called by `render` and its descendants are stored separately from those of
+/// the calling component, much like those of a [`Keyed`] component. When `render` is
+/// skipped, they keep their values as if they had been called in the same order as in
+/// the last render, so memoized components follow the same rules as any other component,
+/// as described in [`use_state`]. Similarly, the focusable components, [`use_event`]
+/// listeners, and other registrations made while rendering are made again whenever the
+/// cached element is reused.
+///
+/// [`component` attribute macro]: ../attr.component.html
+/// [`Derived`]: struct.Derived.html
+/// [`Keyed`]: ../components/struct.Keyed.html
+/// [`Ref`]: struct.Ref.html
+/// [`State`]: struct.State.html
+/// [`State::get`]: struct.State.html#method.get
+/// [`State::inspect`]: struct.State.html#method.inspect
+/// [`use_event`]: ../event/fn.use_event.html
+/// [`use_focus`]: ../focus/fn.use_focus.html
+/// [`use_receiver`]: fn.use_receiver.html
+/// [`use_state`]: fn.use_state.html
+/// [`use_task`]: fn.use_task.html
+#[track_caller]
+pub fn use_memo<P, F>(props: &P, render: F) -> AnyElement
+where
+  P: 'static + Clone + PartialEq + Send,
+  F: FnOnce() -> AnyElement,
+{
+  let cache: super::Ref<Option<Cache<P>>> = use_ref(|| None);
+  let focus = focus::fingerprint();
+
+  let cached = cache.inspect(|cache| match cache {
+    Some(cache) if !cache.recording.dirty.load(Ordering::SeqCst) && cache.props == *props && cache.focus == focus => {
+      Some((cache.element.clone(), cache.recording.clone()))
+    }
+    _ => None,
+  });
+
+  if let Some((element, recording)) = cached {
+    retain_key(MEMO_KEY);
+
+    for dependents in &recording.dependents {
+      dependents.track();
+    }
+
+    for effect in recording.effects {
+      replay(effect);
+    }
+
+    return element;
+  }
+
+  RECORDINGS.lock().push(Recording::default());
+
+  let element = {
+    let _scope = key_scope(MEMO_KEY);
+    render()
+  };
+
+  let recording = RECORDINGS.lock().pop().expect("missing recording");
+
+  cache.set(Some(Cache {
+    props: props.clone(),
+    focus,
+    element: element.clone(),
+    recording,
+  }));
+
+  element
+}
+
+#[cfg(test)]
+mod tests {
+  use serial_test::serial;
+
+  use crate::{
+    component,
+    components::{Any as AnyComponent, Empty, Focusable, VStack},
+    focus, render,
+    state::{self, use_state, Ref, State},
+  };
+
+  #[test]
+  #[serial]
+  fn memo_skips_unchanged_components() {
+    #[component(Counter, memo)]
+    fn render(label: String, shared: State<i32>, count: Ref<Option<State<i32>>>, log: Ref<Vec<String>>) {
+      let own = use_state(|| 0);
+      count.set(Some(own.clone()));
+
+      log.mutate(|log| log.push(format!("{} {} {}", label, own.get(), shared.get())));
+
+      render! {
+        Focusable() { Empty() }
+      }
+    }
+
+    #[component(Root)]
+    fn render(label: String, shared: State<i32>, count: Ref<Option<State<i32>>>, log: Ref<Vec<String>>) {
+      let _ = use_state(|| ());
+
+      render! {
+        VStack() {
+          Counter(label: label.clone(), shared: shared.clone(), count: count.clone(), log: log.clone())
+        }
+      }
+    }
+
+    state::reset();
+    // clear the focus left by other tests, as focus changes re-render memoized components
+    focus::render_done();
+
+    let shared = State::new(0);
+    let count = Ref::new(None);
+    let log = Ref::new(Vec::new());
+
+    let render = |label: &str| {
+      log.set(Vec::new());
+
+      let root: AnyComponent = render! {
+        Root(label, shared: shared.clone(), count: count.clone(), log: log.clone())
+      };
+      root.render();
+      state::render_done().unwrap();
+
+      // the focusable component is registered even when the render is skipped
+      assert_eq!(focus::fingerprint().0, 1);
+      focus::render_done();
+
+      log.get()
+    };
+
+    assert_eq!(render("a"), ["a 0 0"]);
+    assert!(render("a").is_empty());
+
+    // props changed
+    assert_eq!(render("b"), ["b 0 0"]);
+
+    // state called within the component changed
+    count.get().unwrap().set(1);
+    assert_eq!(render("b"), ["b 1 0"]);
+
+    // state read by the component changed
+    shared.set(2);
+    assert_eq!(render("b"), ["b 1 2"]);
+    assert!(render("b").is_empty());
+  }
+}
@@ -2,13 +2,26 @@
 
 mod hook;
 mod manager;
+mod memo;
+mod receiver;
+mod subscription;
+mod task;
 
-use std::sync::Arc;
+use std::{mem, sync::Arc};
 
 use parking_lot::Mutex;
 
-pub(crate) use self::hook::render_done;
-pub use self::hook::use_state;
+#[cfg(test)]
+pub(crate) use self::hook::reset;
+pub(crate) use self::hook::{key_scope, render_done};
+pub(crate) use self::memo::{effect, Dependents};
+use self::subscription::Subscribers;
+pub use self::{
+  hook::{component_scope, use_receiver, use_ref, use_state, use_task, ComponentScope},
+  memo::use_memo,
+  subscription::{Derived, Subscription},
+  task::TaskState,
+};
 use crate::event;
 
 /// A struct that triggers a re-render upon mutation.
@@ -30,24 +43,65 @@ use crate::event;
 ///
 /// This is useful when receiving a `State` as a parameter from a parent component,
 /// as it must be cloned, and then may be mutated by both the child and parent components.
-#[derive(Default)]
+///
+/// # Reacting to Changes
+/// Code outside of components can react to changes of a `State` through
+/// [`State::subscribe`], and values can be derived from a `State` through [`State::map`]:
+/// ```rust
+/// # use intuitive::state::use_state;
+/// #
+/// let count = use_state(|| 1);
+/// let doubled = count.map(|count| count * 2);
+///
+/// let subscription = doubled.subscribe(|doubled| println!("doubled is now {}", doubled));
+///
+/// count.set(2);
+/// assert_eq!(doubled.get(), 4);
+///
+/// // stop printing on changes
+/// drop(subscription);
+/// ```
+///
+/// [`State::map`]: #method.map
+/// [`State::subscribe`]: #method.subscribe
 pub struct State<T> {
   inner: Arc<Mutex<T>>,
+  subscribers: Arc<Subscribers<T>>,
+  dependents: Dependents,
 }
 
 impl<T> State<T> {
   pub(crate) fn new(inner: T) -> Self {
     Self {
       inner: Arc::new(Mutex::new(inner)),
+      subscribers: Arc::default(),
+      dependents: Dependents::default(),
     }
   }
 
-  /// Sets a new value for the state and triggers a re-render.
-  pub fn set(&self, new: T) {
+  /// Calls a function on a mutable reference of the inner value, notifies any subscribers,
+  /// and optionally triggers a re-render.
+  fn write<F, R>(&self, f: F, render: bool) -> R
+  where
+    F: FnOnce(&mut T) -> R,
+  {
     let mut inner = self.inner.lock();
-    *inner = new;
+    let result = f(&mut inner);
+    self.subscribers.notify(&inner);
+    drop(inner);
+
+    self.dependents.invalidate();
 
-    event::re_render().expect("re_render");
+    if render {
+      event::re_render().expect("re_render");
+    }
+
+    result
+  }
+
+  /// Sets a new value for the state and triggers a re-render.
+  pub fn set(&self, new: T) {
+    self.write(|inner| *inner = new, true);
   }
 
   /// Calls a function on the inner value and returns its result.
@@ -56,6 +110,8 @@ impl<T> State<T> {
   where
     F: FnOnce(&T) -> R,
   {
+    self.dependents.track();
+
     f(&self.inner.lock())
   }
 
@@ -64,10 +120,7 @@ impl<T> State<T> {
   where
     F: FnOnce(&mut T) -> R,
   {
-    let mut inner = self.inner.lock();
-    drop(f(&mut inner));
-
-    event::re_render().expect("re_render");
+    self.write(|inner| drop(f(inner)), true);
   }
 
   /// Calls a function on the inner value, replaces it with the result, and triggers a re-render.
@@ -75,22 +128,135 @@ impl<T> State<T> {
   where
     F: FnOnce(&T) -> T,
   {
-    let mut inner = self.inner.lock();
-    *inner = f(&inner);
+    self.write(|inner| *inner = f(inner), true);
+  }
+}
 
-    event::re_render().expect("re_render");
+impl<T: 'static + Send> State<T> {
+  /// Calls `callback` with the new value every time the state is mutated.
+  ///
+  /// The callback is called on the thread that mutated the state, while the state is
+  /// locked, so it must not access the state it is subscribed to. The callback stays
+  /// subscribed until the returned [`Subscription`] is dropped.
+  ///
+  /// [`Subscription`]: struct.Subscription.html
+  pub fn subscribe<F>(&self, callback: F) -> Subscription
+  where
+    F: Fn(&T) + 'static + Send + Sync,
+  {
+    self.subscribers.subscribe(callback)
+  }
+
+  /// Creates a value derived from this state, that is recomputed with `f` every time
+  /// the state is mutated.
+  ///
+  /// See [`Derived`] for details.
+  ///
+  /// [`Derived`]: struct.Derived.html
+  pub fn map<U, F>(&self, f: F) -> Derived<U>
+  where
+    U: 'static + Send,
+    F: Fn(&T) -> U + 'static + Send + Sync,
+  {
+    Derived::new(self, f)
   }
 }
 
 impl<T: Clone> State<T> {
   /// Returns a clone of the `State<T>`'s inner value.
   pub fn get(&self) -> T {
+    self.dependents.track();
+
     self.inner.lock().clone()
   }
 }
 
+impl<T: Default> Default for State<T> {
+  fn default() -> Self {
+    Self::new(T::default())
+  }
+}
+
 impl<T> Clone for State<T> {
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+      subscribers: self.subscribers.clone(),
+      dependents: self.dependents.clone(),
+    }
+  }
+}
+
+/// `State`s are equal if they were cloned from one another.
+impl<T> PartialEq for State<T> {
+  fn eq(&self, other: &Self) -> bool {
+    Arc::ptr_eq(&self.inner, &other.inner)
+  }
+}
+
+/// A struct holding a value across renders, that does not trigger re-renders.
+///
+/// `Ref`s are returned by [`use_ref`], and like [`State`]s, they have interior mutability,
+/// and clones of a `Ref` share the same inner value. Unlike [`State`]s, mutating a `Ref`
+/// never triggers a re-render.
+///
+/// [`State`]: struct.State.html
+/// [`use_ref`]: fn.use_ref.html
+#[derive(Default)]
+pub struct Ref<T> {
+  inner: Arc<Mutex<T>>,
+}
+
+impl<T> Ref<T> {
+  pub(crate) fn new(inner: T) -> Self {
+    Self {
+      inner: Arc::new(Mutex::new(inner)),
+    }
+  }
+
+  /// Sets a new value for the ref.
+  pub fn set(&self, new: T) {
+    *self.inner.lock() = new;
+  }
+
+  /// Calls a function on the inner value and returns its result.
+  pub fn inspect<F, R>(&self, f: F) -> R
+  where
+    F: FnOnce(&T) -> R,
+  {
+    f(&self.inner.lock())
+  }
+
+  /// Calls a function on a mutable reference of the inner value and returns its result.
+  pub fn mutate<F, R>(&self, f: F) -> R
+  where
+    F: FnOnce(&mut T) -> R,
+  {
+    f(&mut self.inner.lock())
+  }
+
+  /// Replaces the inner value, returning the previous one.
+  pub fn replace(&self, new: T) -> T {
+    mem::replace(&mut self.inner.lock(), new)
+  }
+}
+
+impl<T: Clone> Ref<T> {
+  /// Returns a clone of the `Ref<T>`'s inner value.
+  pub fn get(&self) -> T {
+    self.inner.lock().clone()
+  }
+}
+
+impl<T> Clone for Ref<T> {
   fn clone(&self) -> Self {
     Self { inner: self.inner.clone() }
   }
 }
+
+/// `Ref`s are equal if they were cloned from one another.
+impl<T> PartialEq for Ref<T> {
+  fn eq(&self, other: &Self) -> bool {
+    Arc::ptr_eq(&self.inner, &other.inner)
+  }
+}
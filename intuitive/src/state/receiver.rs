@@ -0,0 +1,85 @@
+use std::{
+  collections::VecDeque,
+  sync::{Arc, Weak},
+  thread,
+};
+
+use parking_lot::Mutex;
+
+use super::Dependents;
+use crate::event;
+
+/// The value stored by [`use_receiver`] across renders.
+///
+/// Items received from the source are queued by a forwarding thread, and are only
+/// reduced into the value when the hook is called. A re-render is only triggered
+/// when an item is queued onto an empty queue, so that bursts of items result in a
+/// single render.
+///
+/// [`use_receiver`]: fn.use_receiver.html
+pub(super) struct Receiving<T, S> {
+  queue: Arc<Mutex<VecDeque<T>>>,
+  value: Mutex<S>,
+  dependents: Dependents,
+}
+
+impl<T, S> Receiving<T, S>
+where
+  T: 'static + Send,
+{
+  pub fn new<I>(source: I, value: S) -> Self
+  where
+    I: 'static + Iterator<Item = T> + Send,
+  {
+    let queue = Arc::new(Mutex::new(VecDeque::new()));
+    let dependents = Dependents::default();
+
+    Self::forward(source, Arc::downgrade(&queue), dependents.clone());
+
+    Self {
+      queue,
+      value: Mutex::new(value),
+      dependents,
+    }
+  }
+
+  fn forward<I>(source: I, queue: Weak<Mutex<VecDeque<T>>>, dependents: Dependents)
+  where
+    I: 'static + Iterator<Item = T> + Send,
+  {
+    thread::spawn(move || {
+      for item in source {
+        // the hook is no longer rendered
+        let Some(queue) = queue.upgrade() else {
+          break;
+        };
+
+        let mut queue = queue.lock();
+        queue.push_back(item);
+
+        if queue.len() == 1 {
+          dependents.invalidate();
+          event::re_render().expect("re_render");
+        }
+      }
+    });
+  }
+
+  /// Reduces all queued items into the value, and returns a clone of it.
+  pub fn drain<R>(&self, reducer: R) -> S
+  where
+    S: Clone,
+    R: Fn(&mut S, T),
+  {
+    self.dependents.track();
+
+    let items: Vec<T> = self.queue.lock().drain(..).collect();
+
+    let mut value = self.value.lock();
+    for item in items {
+      reducer(&mut value, item);
+    }
+
+    value.clone()
+  }
+}
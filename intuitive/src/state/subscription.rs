@@ -0,0 +1,225 @@
+use std::sync::{
+  atomic::{AtomicUsize, Ordering},
+  Arc,
+};
+
+use parking_lot::Mutex;
+
+use super::State;
+
+type Callback<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
+/// The callbacks subscribed to a [`State`].
+///
+/// [`State`]: struct.State.html
+pub(super) struct Subscribers<T> {
+  callbacks: Mutex<Vec<(usize, Callback<T>)>>,
+  next_id: AtomicUsize,
+}
+
+impl<T> Default for Subscribers<T> {
+  fn default() -> Self {
+    Self {
+      callbacks: Mutex::new(Vec::new()),
+      next_id: AtomicUsize::new(0),
+    }
+  }
+}
+
+impl<T> Subscribers<T> {
+  pub fn notify(&self, value: &T) {
+    // callbacks are cloned out so that they may (un)subscribe without deadlocking
+    let callbacks: Vec<Callback<T>> = self.callbacks.lock().iter().map(|(_, callback)| callback.clone()).collect();
+
+    for callback in callbacks {
+      callback(value);
+    }
+  }
+}
+
+impl<T: 'static> Subscribers<T> {
+  pub fn subscribe<F>(self: &Arc<Self>, callback: F) -> Subscription
+  where
+    F: Fn(&T) + 'static + Send + Sync,
+  {
+    let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+    self.callbacks.lock().push((id, Arc::new(callback)));
+
+    let subscribers = Arc::downgrade(self);
+
+    Subscription {
+      unsubscribe: Some(Box::new(move || {
+        if let Some(subscribers) = subscribers.upgrade() {
+          subscribers.callbacks.lock().retain(|(other, _)| *other != id);
+        }
+      })),
+    }
+  }
+}
+
+/// A guard returned by [`State::subscribe`], that unsubscribes when dropped.
+///
+/// [`State::subscribe`]: struct.State.html#method.subscribe
+#[must_use = "the callback is unsubscribed as soon as the `Subscription` is dropped"]
+pub struct Subscription {
+  unsubscribe: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl Subscription {
+  /// Keeps the callback subscribed for as long as the [`State`] exists.
+  ///
+  /// [`State`]: struct.State.html
+  pub fn forget(mut self) {
+    self.unsubscribe = None;
+  }
+}
+
+impl Drop for Subscription {
+  fn drop(&mut self) {
+    if let Some(unsubscribe) = self.unsubscribe.take() {
+      unsubscribe();
+    }
+  }
+}
+
+/// A value derived from a [`State`], returned by [`State::map`].
+///
+/// A `Derived` is recomputed every time its source [`State`] is mutated, and stops being
+/// recomputed once it, and all of its clones, are dropped. Recomputing a `Derived` does not
+/// trigger a re-render by itself, as mutating its source already does.
+///
+/// [`State`]: struct.State.html
+/// [`State::map`]: struct.State.html#method.map
+pub struct Derived<T> {
+  state: State<T>,
+  chain: Arc<Chain>,
+}
+
+/// The subscriptions keeping a [`Derived`] value and all of the values it is derived from up to date.
+///
+/// [`Derived`]: struct.Derived.html
+struct Chain {
+  _subscription: Subscription,
+  _parent: Option<Arc<Chain>>,
+}
+
+impl<T: 'static + Send> Derived<T> {
+  pub(super) fn new<S, F>(source: &State<S>, f: F) -> Self
+  where
+    S: 'static + Send,
+    F: Fn(&S) -> T + 'static + Send + Sync,
+  {
+    Self::chained(source, f, None)
+  }
+
+  fn chained<S, F>(source: &State<S>, f: F, parent: Option<Arc<Chain>>) -> Self
+  where
+    S: 'static + Send,
+    F: Fn(&S) -> T + 'static + Send + Sync,
+  {
+    let state = State::new(source.inspect(&f));
+    let target = state.clone();
+
+    let subscription = source.subscribe(move |value| target.write(|target| *target = f(value), false));
+
+    Self {
+      state,
+      chain: Arc::new(Chain {
+        _subscription: subscription,
+        _parent: parent,
+      }),
+    }
+  }
+}
+
+impl<T> Derived<T> {
+  /// Calls a function on the inner value and returns its result.
+  pub fn inspect<F, R>(&self, f: F) -> R
+  where
+    F: FnOnce(&T) -> R,
+  {
+    self.state.inspect(f)
+  }
+}
+
+impl<T: 'static + Send> Derived<T> {
+  /// Calls `callback` with the new value every time this value is recomputed.
+  ///
+  /// See [`State::subscribe`] for details.
+  ///
+  /// [`State::subscribe`]: struct.State.html#method.subscribe
+  pub fn subscribe<F>(&self, callback: F) -> Subscription
+  where
+    F: Fn(&T) + 'static + Send + Sync,
+  {
+    self.state.subscribe(callback)
+  }
+
+  /// Creates a value derived from this one.
+  ///
+  /// See [`State::map`] for details.
+  ///
+  /// [`State::map`]: struct.State.html#method.map
+  pub fn map<U, F>(&self, f: F) -> Derived<U>
+  where
+    U: 'static + Send,
+    F: Fn(&T) -> U + 'static + Send + Sync,
+  {
+    Derived::chained(&self.state, f, Some(self.chain.clone()))
+  }
+}
+
+impl<T: Clone> Derived<T> {
+  /// Returns a clone of the `Derived<T>`'s inner value.
+  pub fn get(&self) -> T {
+    self.state.get()
+  }
+}
+
+impl<T> Clone for Derived<T> {
+  fn clone(&self) -> Self {
+    Self {
+      state: self.state.clone(),
+      chain: self.chain.clone(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn subscribe_until_dropped() {
+    let state = State::new(0);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let subscription = {
+      let seen = seen.clone();
+      state.subscribe(move |value| seen.lock().push(*value))
+    };
+
+    state.set(1);
+    state.update(|value| value + 1);
+    drop(subscription);
+    state.set(3);
+
+    assert_eq!(*seen.lock(), vec![1, 2]);
+  }
+
+  #[test]
+  fn map_recomputes() {
+    let state = State::new(1);
+    let doubled = state.map(|value| value * 2);
+    let described = doubled.map(|value| format!("{}!", value));
+
+    // only the last derived value is kept, which must keep the whole chain alive
+    drop(doubled);
+
+    state.set(2);
+    assert_eq!(described.get(), "4!");
+
+    drop(described);
+    assert!(state.subscribers.callbacks.lock().is_empty());
+  }
+}
@@ -0,0 +1,137 @@
+use std::{
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  },
+  thread,
+};
+
+use parking_lot::Mutex;
+
+use super::{Dependents, Ref};
+use crate::event;
+
+/// The status of a task started by [`use_task`].
+///
+/// [`use_task`]: fn.use_task.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TaskState<T, E> {
+  /// The task is still running.
+  Pending,
+  /// The task finished successfully.
+  Ready(T),
+  /// The task finished with an error.
+  Failed(E),
+}
+
+impl<T, E> TaskState<T, E> {
+  /// Returns whether the task is still running.
+  pub fn is_pending(&self) -> bool {
+    matches!(self, Self::Pending)
+  }
+
+  /// Returns the value of the task, if it finished successfully.
+  pub fn ready(&self) -> Option<&T> {
+    match self {
+      Self::Ready(value) => Some(value),
+      _ => None,
+    }
+  }
+
+  /// Returns the error of the task, if it failed.
+  pub fn failed(&self) -> Option<&E> {
+    match self {
+      Self::Failed(err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+impl<T, E> From<Result<T, E>> for TaskState<T, E> {
+  fn from(result: Result<T, E>) -> Self {
+    match result {
+      Ok(value) => Self::Ready(value),
+      Err(err) => Self::Failed(err),
+    }
+  }
+}
+
+/// The value stored by [`use_task`] across renders.
+///
+/// Each started task is tagged with a generation, and its result is only kept if
+/// no other task was started since, and the hook was not dropped.
+///
+/// [`use_task`]: fn.use_task.html
+pub(super) struct Task<D, T, E> {
+  deps: Mutex<Option<D>>,
+  status: Ref<TaskState<T, E>>,
+  generation: Arc<AtomicUsize>,
+  dependents: Dependents,
+}
+
+impl<D, T, E> Task<D, T, E>
+where
+  D: PartialEq,
+  T: 'static + Send,
+  E: 'static + Send,
+{
+  pub fn new() -> Self {
+    Self {
+      deps: Mutex::new(None),
+      status: Ref::new(TaskState::Pending),
+      generation: Arc::new(AtomicUsize::new(0)),
+      dependents: Dependents::default(),
+    }
+  }
+
+  /// Starts `work` on a new thread if `deps` differ from those of the last started task.
+  pub fn run<F>(&self, deps: D, work: F)
+  where
+    F: 'static + FnOnce() -> Result<T, E> + Send,
+  {
+    let mut current = self.deps.lock();
+    if current.as_ref() == Some(&deps) {
+      return;
+    }
+
+    *current = Some(deps);
+
+    let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+    self.status.set(TaskState::Pending);
+
+    let status = self.status.clone();
+    let current_generation = self.generation.clone();
+    let dependents = self.dependents.clone();
+
+    thread::spawn(move || {
+      let result = work();
+
+      let finished = status.mutate(|status| {
+        if current_generation.load(Ordering::SeqCst) == generation {
+          *status = result.into();
+          true
+        } else {
+          false
+        }
+      });
+
+      if finished {
+        dependents.invalidate();
+        event::re_render().expect("re_render");
+      }
+    });
+  }
+
+  pub fn status(&self) -> Ref<TaskState<T, E>> {
+    self.dependents.track();
+
+    self.status.clone()
+  }
+}
+
+impl<D, T, E> Drop for Task<D, T, E> {
+  fn drop(&mut self) {
+    // discard the result of any running task
+    self.generation.fetch_add(1, Ordering::SeqCst);
+  }
+}
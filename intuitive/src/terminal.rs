@@ -1,26 +1,58 @@
 //! Contains the `Terminal` type used to run the UI.
 
-use std::io::{self, Stdout};
+use std::io;
 
 use crossterm::{
-  event::{DisableMouseCapture, EnableMouseCapture},
+  event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
   execute,
   terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 pub use tui::layout::Rect;
-use tui::{backend::CrosstermBackend, terminal::Frame as TuiFrame, Terminal as TuiTerminal};
+use tui::{terminal::Frame as TuiFrame, Terminal as TuiTerminal};
 
 use crate::{
   components::Any as AnyComponent,
   element::Any as AnyElement,
   error::Result,
-  event::{self, Event, MouseEventKind},
-  state,
+  event::{self, custom, handler::Propagate, mouse, tree, Event},
+  focus, state,
 };
 
-pub type Backend = CrosstermBackend<Stdout>;
+#[cfg(not(test))]
+pub type Backend = tui::backend::CrosstermBackend<io::Stdout>;
+/// Tests draw into an in-memory buffer, rather than the terminal.
+#[cfg(test)]
+pub type Backend = tui::backend::TestBackend;
 pub type Frame<'a> = TuiFrame<'a, Backend>;
 
+/// The width and height of an area of the terminal, measured in cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Size {
+  pub width: u16,
+  pub height: u16,
+}
+
+impl Size {
+  pub fn new(width: u16, height: u16) -> Self {
+    Self { width, height }
+  }
+
+  /// Returns the size shrunk to fit within `other`.
+  #[must_use]
+  pub fn min(self, other: Self) -> Self {
+    Self {
+      width: self.width.min(other.width),
+      height: self.height.min(other.height),
+    }
+  }
+}
+
+impl From<Rect> for Size {
+  fn from(rect: Rect) -> Self {
+    Self::new(rect.width, rect.height)
+  }
+}
+
 pub struct Terminal {
   root: AnyComponent,
   terminal: TuiTerminal<Backend>,
@@ -36,20 +68,30 @@ impl Terminal {
 
     Ok(Self {
       root,
-      terminal: TuiTerminal::new(CrosstermBackend::new(io::stdout()))?,
+      terminal: TuiTerminal::new(Self::backend())?,
     })
   }
 
+  #[cfg(not(test))]
+  fn backend() -> Backend {
+    Backend::new(io::stdout())
+  }
+
+  #[cfg(test)]
+  fn backend() -> Backend {
+    Backend::new(0, 0)
+  }
+
   fn setup() -> Result<()> {
     enable_raw_mode()?;
-    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
 
     Ok(())
   }
 
   fn cleanup(&mut self) -> Result<()> {
     disable_raw_mode()?;
-    execute!(self.terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste)?;
     self.terminal.show_cursor()?;
 
     Ok(())
@@ -57,35 +99,51 @@ impl Terminal {
 
   fn draw(&mut self, element: &AnyElement) -> Result<()> {
     self.terminal.draw(|frame| {
+      tree::begin_frame();
       element.draw(frame.size(), frame);
+      tree::end_frame();
     })?;
 
     Ok(())
   }
 
-  pub fn run(&mut self) -> Result<()> {
-    let mut component = self.root.render();
-    state::render_done();
+  fn render(&self) -> Result<AnyElement> {
+    let component = self.root.render();
+    state::render_done()?;
+    focus::render_done();
+    custom::render_done();
 
-    let mut skip_next_render = false;
+    Ok(component)
+  }
 
+  pub fn run(&mut self) -> Result<()> {
+    let component = self.render()?;
     self.draw(&component)?;
 
     loop {
-      let event = event::read()?;
-
-      if !skip_next_render {
-        component = self.root.render();
-        state::render_done();
-      }
-      skip_next_render = false;
-
-      match event {
-        Event::Render => self.draw(&component)?,
-        Event::Key(event) => component.on_key(event),
-
-        Event::Mouse(event) if event.kind == MouseEventKind::Moved => skip_next_render = true,
-        Event::Mouse(event) => component.on_mouse(self.terminal.size()?, event),
+      // events are dispatched through the elements that were last drawn, which are
+      // redrawn whenever state changes, since that sends an `Event::Render`
+      match event::read()? {
+        Event::Render => {
+          let component = self.render()?;
+          self.draw(&component)?;
+        }
+
+        Event::Key(event) => {
+          if focus::dispatch(event) == Propagate::Next {
+            tree::dispatch_key(event);
+          }
+        }
+
+        Event::Paste(text) => {
+          tree::dispatch_paste(&text);
+        }
+
+        Event::Custom(event) => custom::dispatch(event),
+
+        Event::Mouse(event) => {
+          mouse::dispatch(event);
+        }
 
         Event::Quit => break,
       }
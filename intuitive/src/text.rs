@@ -16,7 +16,7 @@ use crate::style::Style;
 ///
 /// [`Lines`]: struct.Lines.html
 /// [`Spans`]: struct.Spans.html
-#[derive(Default, Clone)]
+#[derive(Default, Clone, PartialEq, Eq)]
 pub struct Span {
   pub text: String,
   pub style: Style,
@@ -75,7 +75,7 @@ impl From<Span> for TuiSpan<'_> {
 /// [`&str`]: https://doc.rust-lang.org/std/primitive.str.html
 /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
 /// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
-#[derive(Default, Clone)]
+#[derive(Default, Clone, PartialEq, Eq)]
 pub struct Spans(pub Vec<Span>);
 
 impl Spans {
@@ -120,7 +120,7 @@ impl Deref for Spans {
   }
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, PartialEq, Eq)]
 /// Multiple lines of text with a variety of styles.
 ///
 /// Each [`Spans`] element in the inner [`Vec`] is considered a line. Components that
@@ -0,0 +1,108 @@
+//! The key string parser shared by the `intuitive` crate and its `on_key!` macro.
+//!
+//! Keys are parsed into a [`Key`] independent of any terminal library, which the
+//! `intuitive` crate converts into its own `Key`, and the `on_key!` macro into an
+//! expression constructing one.
+
+/// A modifier of a [`Key`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Modifier {
+  Control,
+  Alt,
+  Shift,
+  Super,
+}
+
+/// The code of a [`Key`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Code {
+  Char(char),
+  Enter,
+  Esc,
+  Tab,
+  BackTab,
+  Backspace,
+  Delete,
+  Insert,
+  Up,
+  Down,
+  Left,
+  Right,
+  Home,
+  End,
+  PageUp,
+  PageDown,
+  F(u8),
+}
+
+/// A parsed key, with its modifiers in the order they were written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Key {
+  pub code: Code,
+  pub modifiers: Vec<Modifier>,
+}
+
+/// Parses a single key, such as `"ctrl+x"`, returning the reason it is invalid otherwise.
+pub fn parse(s: &str) -> Result<Key, String> {
+  // a trailing `+` is the key itself, as in `ctrl++`
+  let (modifiers, name) = match s.strip_suffix("++") {
+    Some(modifiers) => (modifiers, "+"),
+    None => s.rsplit_once('+').unwrap_or(("", s)),
+  };
+
+  if name.is_empty() {
+    return Err(String::from("missing key name"));
+  }
+
+  let mut parsed = Vec::new();
+  for name in modifiers.split('+').filter(|_| !modifiers.is_empty()) {
+    let modifier = match name.to_ascii_lowercase().as_str() {
+      "ctrl" | "control" => Modifier::Control,
+      "alt" | "meta" => Modifier::Alt,
+      "shift" => Modifier::Shift,
+      "super" | "cmd" => Modifier::Super,
+
+      "" => return Err(String::from("empty modifier")),
+      _ => return Err(format!("unknown modifier `{}`", name)),
+    };
+
+    if parsed.contains(&modifier) {
+      return Err(format!("duplicate modifier `{}`", name));
+    }
+
+    parsed.push(modifier);
+  }
+
+  let mut chars = name.chars();
+  let code = match (chars.next(), chars.next()) {
+    (Some(c), None) => Code::Char(c),
+
+    _ => match name.to_ascii_lowercase().as_str() {
+      "space" => Code::Char(' '),
+      "plus" => Code::Char('+'),
+      "minus" => Code::Char('-'),
+      "enter" | "return" => Code::Enter,
+      "esc" | "escape" => Code::Esc,
+      "tab" => Code::Tab,
+      "backtab" => Code::BackTab,
+      "backspace" => Code::Backspace,
+      "delete" | "del" => Code::Delete,
+      "insert" | "ins" => Code::Insert,
+      "up" => Code::Up,
+      "down" => Code::Down,
+      "left" => Code::Left,
+      "right" => Code::Right,
+      "home" => Code::Home,
+      "end" => Code::End,
+      "pageup" => Code::PageUp,
+      "pagedown" => Code::PageDown,
+
+      lower => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+        Some(n) if (1..=12).contains(&n) => Code::F(n),
+        _ => return Err(format!("unknown key `{}`", name)),
+      },
+    },
+  };
+
+  Ok(Key { code, modifiers: parsed })
+}
@@ -1,8 +1,10 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{
   parse::{Parse, ParseStream},
-  parse_macro_input, FnArg, Generics, Ident, ItemFn, Pat, PatType, Result, ReturnType,
+  parse_macro_input, Attribute, Error, Expr, FnArg, GenericArgument, Generics, Ident, ItemFn, Pat, PatIdent, PatType, PathArguments,
+  Result, ReturnType, Token, Type,
 };
 
 use crate::utils;
@@ -10,62 +12,217 @@ use crate::utils;
 pub struct Component {
   name: Ident,
   generics: Generics,
+  memo: bool,
 }
 
 impl Parse for Component {
   fn parse(input: ParseStream) -> Result<Self> {
+    let name = input.parse()?;
+    let generics = input.parse()?;
+
+    let mut memo = false;
+    if input.peek(Token![,]) {
+      input.parse::<Token![,]>()?;
+
+      let option: Ident = input.parse()?;
+      if option != "memo" {
+        return Err(Error::new(option.span(), "unknown option, expected `memo`"));
+      }
+
+      memo = true;
+    }
+
+    Ok(Self { name, generics, memo })
+  }
+}
+
+/// A parameter of a functional component.
+struct Param {
+  /// The attributes of the parameter, other than `#[default(..)]`, applied to its field.
+  attrs: Vec<Attribute>,
+  ident: Ident,
+  pat: Box<Pat>,
+  ty: Box<Type>,
+  default: Option<Expr>,
+  /// Whether the parameter is an `Option<T>`, stored as an `Optional<T>`.
+  optional: bool,
+}
+
+impl Param {
+  fn new(input: &FnArg) -> Result<Self> {
+    let PatType { attrs, pat, ty, .. } = match input {
+      FnArg::Receiver(receiver) => return Err(Error::new_spanned(receiver, "receivers not allowed in functional component")),
+      FnArg::Typed(pat_type) => pat_type,
+    };
+
+    let ident = match &**pat {
+      Pat::Ident(PatIdent { ident, .. }) => ident.clone(),
+      pat => return Err(Error::new_spanned(pat, "parameters must be identifiers")),
+    };
+
+    let mut default = None;
+    let mut field_attrs = Vec::new();
+    for attr in attrs {
+      if !attr.path.is_ident("default") {
+        field_attrs.push(attr.clone());
+      } else if default.is_none() {
+        default = Some(attr.parse_args()?);
+      } else {
+        return Err(Error::new_spanned(attr, "duplicate `#[default(..)]` attribute"));
+      }
+    }
+
     Ok(Self {
-      name: input.parse()?,
-      generics: input.parse()?,
+      attrs: field_attrs,
+      ident,
+      pat: pat.clone(),
+      optional: option_inner(ty).is_some(),
+      ty: ty.clone(),
+      default,
     })
   }
+
+  /// Returns the type of the field storing the parameter.
+  fn field_ty(&self, crate_name: &TokenStream2) -> TokenStream2 {
+    let ty = &self.ty;
+
+    match option_inner(ty) {
+      Some(inner) => quote! { #crate_name::components::param::Optional<#inner> },
+      None => quote! { #ty },
+    }
+  }
+}
+
+/// Returns `T` if `ty` is an `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+  let path = match ty {
+    Type::Path(path) => path,
+    _ => return None,
+  };
+
+  let segment = path.path.segments.last()?;
+  if path.qself.is_some() || segment.ident != "Option" {
+    return None;
+  }
+
+  match &segment.arguments {
+    PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+      GenericArgument::Type(inner) => Some(inner),
+      _ => None,
+    },
+    _ => None,
+  }
 }
 
 /// The implementation of the `component` attribute macro. See the
 /// docs at the root of the crate for details.
 pub fn parse(attr: TokenStream, item: TokenStream) -> TokenStream {
-  let Component { name, generics } = parse_macro_input!(attr as Component);
+  let Component { name, generics, memo } = parse_macro_input!(attr as Component);
   let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
   let crate_name = utils::crate_name();
 
-  let ItemFn { attrs, vis, sig, block } = syn::parse(item).unwrap();
-  let params: Vec<_> = sig.inputs.iter().collect();
+  let ItemFn { attrs, vis, sig, block } = parse_macro_input!(item as ItemFn);
 
   let retty = match sig.output {
     ReturnType::Default => syn::parse(quote! { -> #crate_name::element::Any }.into()).unwrap(),
     retty => retty,
   };
 
-  let param_names: Vec<Box<Pat>> = params
-    .iter()
-    .map(|input| match input {
-      FnArg::Receiver { .. } => panic!("receivers not allowed in functional component"),
-      FnArg::Typed(PatType { pat, .. }) => pat,
-    })
-    .cloned()
-    .collect();
+  let params = match sig.inputs.iter().map(Param::new).collect::<Result<Vec<_>>>() {
+    Ok(params) => params,
+    Err(err) => return err.to_compile_error().into(),
+  };
+
+  let fields = params.iter().map(|param| {
+    let Param { attrs, ident, .. } = param;
+    let ty = param.field_ty(&crate_name);
+
+    quote! { #(#attrs)* pub #ident: #ty }
+  });
+
+  let args = params.iter().map(|Param { pat, ty, .. }| quote! { #pat: #ty });
+  let values = params.iter().map(|Param { ident, optional, .. }| match optional {
+    true => quote! { #ident: #ident.into() },
+    false => quote! { #ident },
+  });
+
+  let bindings = params.iter().map(|Param { ident, pat, optional, .. }| match optional {
+    true => quote! { #ident: #crate_name::components::param::Optional(#pat) },
+    false => quote! { #pat },
+  });
+
+  // memoized components are compared to, and cloned into, the cache of the previous render
+  let (memo_derive, body) = if memo {
+    let body = quote! {
+      #crate_name::state::use_memo(self, || #retty {
+        let #name{ #(#bindings),* } = self;
+
+        #block
+      })
+    };
+
+    (quote! { #[derive(Clone, PartialEq)] }, body)
+  } else {
+    let body = quote! {
+      let #name{ #(#bindings),* } = self;
+
+      #block
+    };
+
+    (quote! {}, body)
+  };
+
+  // the struct only derives `Default` if none of the parameters have a default value, as
+  // the derive would otherwise require generic parameters to implement `Default`
+  let (derive, default_impl) = if params.iter().all(|param| param.default.is_none()) {
+    (quote! { #[derive(Default)] }, quote! {})
+  } else {
+    let defaults = params.iter().map(|Param { ident, default, .. }| match default {
+      Some(default) => {
+        let value = utils::from_param(default, &crate_name);
+        quote! { #ident: #value }
+      }
+      None => quote! { #ident: ::std::default::Default::default() },
+    });
+
+    let default_impl = quote! {
+      impl #impl_generics ::std::default::Default for #name #ty_generics #where_clause {
+        fn default() -> Self {
+          Self {
+            #(#defaults),*
+          }
+        }
+      }
+    };
+
+    (quote! {}, default_impl)
+  };
 
   quote! {
     #(#attrs)*
-    #[derive(Default)]
+    #derive
+    #memo_derive
     #vis struct #name #generics {
-      #(pub #params),*
+      #(#fields),*
     }
 
+    #default_impl
+
     impl #impl_generics #name #ty_generics #where_clause {
-      pub fn new(#(#params),*) -> #crate_name::components::Any {
+      #[allow(clippy::too_many_arguments)]
+      pub fn new(#(#args),*) -> #crate_name::components::Any {
         Self {
-          #(#param_names),*
+          #(#values),*
         }.into()
       }
     }
 
     impl #impl_generics #crate_name::components::Component for #name #ty_generics #where_clause {
       fn render(&self) #retty {
-        let #name{ #(#param_names),* } = self;
+        let _scope = #crate_name::state::component_scope(stringify!(#name));
 
-        #block
+        #body
       }
     }
   }
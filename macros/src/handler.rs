@@ -0,0 +1,120 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+  bracketed,
+  parse::{Parse, ParseStream},
+  parse_quote,
+  punctuated::Punctuated,
+  token::Bracket,
+  Arm, Attribute, Error, Expr, Ident, Result, Stmt, Token,
+};
+
+/// The input shared by the `on_key` and `on_mouse` macros: an optional `#[propagate]`
+/// attribute, an optional list of captured state, and the arms of the `match`.
+pub struct Handler {
+  pub propagate: bool,
+  pub capture: Punctuated<Ident, Token![,]>,
+  pub arms: Vec<Arm>,
+}
+
+impl Parse for Handler {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let mut propagate = false;
+    for attr in input.call(Attribute::parse_outer)? {
+      if attr.path.is_ident("propagate") && attr.tokens.is_empty() {
+        propagate = true;
+      } else {
+        return Err(Error::new_spanned(attr, "expected `#[propagate]`"));
+      }
+    }
+
+    let lookahead = input.lookahead1();
+    let capture = if lookahead.peek(Bracket) {
+      let content;
+      bracketed!(content in input);
+
+      content.parse_terminated(Ident::parse)?
+    } else {
+      Punctuated::new()
+    };
+
+    Ok(Self {
+      propagate,
+      capture,
+      arms: parse_arms(input)?,
+    })
+  }
+}
+
+/// Returns whether `expr` always returns or panics, rather than evaluating to a value.
+fn diverges(expr: &Expr) -> bool {
+  match expr {
+    Expr::Return(_) => true,
+    Expr::Macro(mac) => ["panic", "unreachable", "todo", "unimplemented"]
+      .iter()
+      .any(|name| mac.mac.path.is_ident(name)),
+    Expr::Block(block) => match block.block.stmts.last() {
+      Some(Stmt::Expr(expr) | Stmt::Semi(expr, _)) => diverges(expr),
+      _ => false,
+    },
+    _ => false,
+  }
+}
+
+fn parse_arms(input: ParseStream) -> Result<Vec<Arm>> {
+  let mut arms = Vec::new();
+  while !input.is_empty() {
+    // remove commas from arms, we add them later in `expand`
+    let mut arm = input.call(Arm::parse)?;
+    arm.comma = None;
+
+    arms.push(arm);
+  }
+
+  Ok(arms)
+}
+
+impl Handler {
+  /// Expands into a closure taking an `event` of type `event_type`, with `uses` brought
+  /// into scope, that `match`es the event against the arms.
+  pub fn expand(self, event_type: TokenStream, uses: TokenStream, crate_name: &TokenStream) -> TokenStream {
+    let Self {
+      propagate,
+      capture,
+      mut arms,
+    } = self;
+    let capture = capture.iter();
+
+    // arms evaluate to either `()` or a `Propagate`, and may `return` from the handler,
+    // in which case they are left as they are, since there is no value to convert
+    for arm in arms.iter_mut().filter(|arm| !diverges(&arm.body)) {
+      let body = &arm.body;
+      arm.body = parse_quote! { ::std::convert::Into::<#crate_name::event::handler::Propagate>::into({ #body }) };
+    }
+
+    let fallback = if propagate {
+      quote! { #crate_name::event::handler::Propagate::Next }
+    } else {
+      quote! { #crate_name::event::handler::Propagate::Stop }
+    };
+
+    quote! {
+      {
+        #(let #capture = #capture.clone();)*
+
+        move |event: #event_type| {
+          use #uses;
+
+          let propagate = match event {
+            #(#arms,)*
+
+            #[allow(unreachable_patterns)]
+            _ => #fallback,
+          };
+
+          propagate
+        }
+      }
+    }
+  }
+}
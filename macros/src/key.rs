@@ -0,0 +1,54 @@
+use intuitive_keys::{Code, Modifier};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, LitStr, Result};
+
+/// Parses a key string like `"ctrl+a"` into an expression constructing the equivalent
+/// `intuitive::event::keymap::Key`.
+///
+/// This uses the same parser as the `FromStr` implementation of `Key`, so that invalid
+/// keys are reported at compile time.
+pub fn parse(lit: &LitStr, crate_name: &TokenStream) -> Result<TokenStream> {
+  let s = lit.value();
+  let invalid = |reason: &str| Error::new(lit.span(), format!("invalid key `{}`: {}", s, reason));
+
+  if s.split_whitespace().count() > 1 {
+    return Err(invalid("key sequences are not supported, use a `Keymap` instead"));
+  }
+
+  let key = intuitive_keys::parse(&s).map_err(|reason| invalid(&reason))?;
+
+  let code = match key.code {
+    Code::Char(c) => quote! { Char(#c) },
+    Code::Enter => quote! { Enter },
+    Code::Esc => quote! { Esc },
+    Code::Tab => quote! { Tab },
+    Code::BackTab => quote! { BackTab },
+    Code::Backspace => quote! { Backspace },
+    Code::Delete => quote! { Delete },
+    Code::Insert => quote! { Insert },
+    Code::Up => quote! { Up },
+    Code::Down => quote! { Down },
+    Code::Left => quote! { Left },
+    Code::Right => quote! { Right },
+    Code::Home => quote! { Home },
+    Code::End => quote! { End },
+    Code::PageUp => quote! { PageUp },
+    Code::PageDown => quote! { PageDown },
+    Code::F(n) => quote! { F(#n) },
+  };
+
+  let modifiers = key.modifiers.iter().map(|modifier| match modifier {
+    Modifier::Control => quote! { CONTROL },
+    Modifier::Alt => quote! { ALT },
+    Modifier::Shift => quote! { SHIFT },
+    Modifier::Super => quote! { SUPER },
+  });
+
+  Ok(quote! {
+    #crate_name::event::keymap::Key::new(
+      #crate_name::event::KeyCode::#code,
+      #crate_name::event::KeyModifiers::NONE #(| #crate_name::event::KeyModifiers::#modifiers)*
+    )
+  })
+}
@@ -1,5 +1,8 @@
 mod component;
+mod handler;
+mod key;
 mod on_key;
+mod on_mouse;
 mod render;
 
 mod utils;
@@ -35,3 +38,13 @@ pub fn render(item: TokenStream) -> TokenStream {
 pub fn on_key(item: TokenStream) -> TokenStream {
   on_key::parse(item)
 }
+
+/// Helper macro for creating mouse handlers.
+///
+/// See the documentation in the [`intuitive`] crate for details.
+///
+/// [`intuitive`]: https://docs.rs/intuitive/latest/intuitive/macro.on_mouse.html
+#[proc_macro]
+pub fn on_mouse(item: TokenStream) -> TokenStream {
+  on_mouse::parse(item)
+}
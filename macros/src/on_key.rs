@@ -1,78 +1,82 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{
-  bracketed,
-  parse::{Parse, ParseStream},
-  parse_macro_input,
-  punctuated::Punctuated,
-  token::Bracket,
-  Arm, Ident, Result, Token,
-};
+use syn::{parse_macro_input, parse_quote, Arm, Error, Expr, Lit, LitStr, Pat, Result};
 
-use crate::utils;
+use crate::{handler::Handler, key, utils};
 
-struct OnKey {
-  capture: Punctuated<Ident, Token![,]>,
-  arms: Vec<Arm>,
-}
+/// Returns the key strings of a pattern like `"ctrl+a"` or `"up" | "k"`, or `None` if the
+/// pattern does not contain any strings.
+fn key_strings(pat: &Pat) -> Result<Option<Vec<&LitStr>>> {
+  let cases: Vec<&Pat> = match pat {
+    Pat::Or(or) => or.cases.iter().collect(),
+    pat => vec![pat],
+  };
 
-impl Parse for OnKey {
-  fn parse(input: ParseStream) -> Result<Self> {
-    let lookahead = input.lookahead1();
-    if lookahead.peek(Bracket) {
-      let content;
-      bracketed!(content in input);
+  let strings: Vec<_> = cases
+    .iter()
+    .map(|case| match case {
+      Pat::Lit(lit) => match &*lit.expr {
+        Expr::Lit(expr) => match &expr.lit {
+          Lit::Str(lit) => Some(lit),
+          _ => None,
+        },
+        _ => None,
+      },
+      _ => None,
+    })
+    .collect();
 
-      Ok(Self {
-        capture: content.parse_terminated(Ident::parse)?,
-        arms: parse_arms(input)?,
-      })
-    } else {
-      Ok(Self {
-        capture: Punctuated::new(),
-        arms: parse_arms(input)?,
-      })
-    }
+  match strings.iter().filter(|string| string.is_some()).count() {
+    0 => Ok(None),
+    n if n == strings.len() => Ok(Some(strings.into_iter().flatten().collect())),
+    _ => Err(Error::new_spanned(pat, "key strings cannot be mixed with other patterns")),
   }
 }
 
-fn parse_arms(input: ParseStream) -> Result<Vec<Arm>> {
-  let mut arms = Vec::new();
-  while !input.is_empty() {
-    // remove commas from arms, we add them later in `parse`
-    let mut arm = input.call(Arm::parse)?;
-    arm.comma = None;
+/// Replaces an arm matching key strings with a wildcard arm guarded by a comparison
+/// against the parsed keys.
+fn expand_key_strings(arm: &mut Arm, crate_name: &TokenStream2) -> Result<()> {
+  let keys = match key_strings(&arm.pat)? {
+    Some(strings) => strings
+      .into_iter()
+      .map(|string| key::parse(string, crate_name))
+      .collect::<Result<Vec<_>>>()?,
+    None => return Ok(()),
+  };
 
-    arms.push(arm);
-  }
+  let matches = quote! {
+    (#(#crate_name::event::keymap::Key::from(event) == #keys)||*)
+  };
+
+  let guard: Expr = match arm.guard.take() {
+    Some((_, guard)) => parse_quote! { #matches && (#guard) },
+    None => parse_quote! { #matches },
+  };
+
+  arm.pat = parse_quote! { _ };
+  arm.guard = Some((Default::default(), Box::new(guard)));
 
-  Ok(arms)
+  Ok(())
 }
 
 /// The implementation of the `on_key` function-like macro. See the
 /// docs at the root of the crate for details.
 pub fn parse(input: TokenStream) -> TokenStream {
-  let OnKey { capture, arms } = parse_macro_input!(input as OnKey);
-  let arms = arms.iter();
-  let capture = capture.iter();
+  let mut handler = parse_macro_input!(input as Handler);
   let crate_name = utils::crate_name();
 
-  quote! {
-    {
-      #(let #capture = #capture.clone();)*
-
-      move |event| {
-        use #crate_name::event::{self, KeyCode::*, KeyEvent, KeyModifiers, handler::Propagate};
-
-        match event {
-          #(#arms,)*
-
-          _ => (),
-        };
-
-        #crate_name::event::handler::Propagate::Stop
-      }
+  for arm in &mut handler.arms {
+    if let Err(err) = expand_key_strings(arm, &crate_name) {
+      return err.into_compile_error().into();
     }
   }
-  .into()
+
+  handler
+    .expand(
+      quote! { #crate_name::event::KeyEvent },
+      quote! { #crate_name::event::{self, KeyCode::*, KeyEvent, KeyModifiers, handler::Propagate} },
+      &crate_name,
+    )
+    .into()
 }
@@ -0,0 +1,91 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Error, Pat, Result};
+
+use crate::{handler::Handler, utils};
+
+/// Returns the pattern for a shorthand like `left_click` or `scroll_up`.
+///
+/// Identifiers that are not shorthands are returned as `Ok(None)`, and bind the event as
+/// usual. Identifiers that look like a shorthand, starting with a button or ending with an
+/// action, but are not one, are an error, since they would otherwise silently match every
+/// event.
+fn shorthand(name: &str) -> std::result::Result<Option<TokenStream2>, String> {
+  let (prefix, suffix) = match name {
+    "moved" => return Ok(Some(quote! { Moved })),
+    "scroll_up" => return Ok(Some(quote! { ScrollUp })),
+    "scroll_down" => return Ok(Some(quote! { ScrollDown })),
+
+    name => match name.split_once('_') {
+      Some(parts) => parts,
+      None => return Ok(None),
+    },
+  };
+
+  let button = match prefix {
+    "left" => Some(quote! { Left }),
+    "right" => Some(quote! { Right }),
+    "middle" => Some(quote! { Middle }),
+    _ => None,
+  };
+
+  let action = match suffix {
+    "click" => Some(quote! { Down }),
+    "release" => Some(quote! { Up }),
+    "drag" => Some(quote! { Drag }),
+    _ => None,
+  };
+
+  match (button, action) {
+    (Some(button), Some(action)) => Ok(Some(quote! { #action(MouseButton::#button) })),
+    (None, None) if prefix != "scroll" => Ok(None),
+
+    _ => Err(format!(
+      "unknown mouse shorthand `{}`, expected `moved`, `scroll_up`, `scroll_down`, or one of `left`, `right`, \
+       or `middle` followed by `_click`, `_release`, or `_drag`",
+      name
+    )),
+  }
+}
+
+/// Replaces shorthands in a pattern, including within `|` patterns, with patterns matching
+/// the kind of the event.
+fn expand_shorthands(pat: &mut Pat) -> Result<()> {
+  match pat {
+    Pat::Or(or) => or.cases.iter_mut().try_for_each(expand_shorthands),
+
+    Pat::Ident(ident) if ident.by_ref.is_none() && ident.mutability.is_none() && ident.subpat.is_none() => {
+      let kind = shorthand(&ident.ident.to_string()).map_err(|message| Error::new(ident.ident.span(), message))?;
+
+      if let Some(kind) = kind {
+        *pat = parse_quote! { MouseEvent { kind: MouseEventKind::#kind, .. } };
+      }
+
+      Ok(())
+    }
+
+    _ => Ok(()),
+  }
+}
+
+/// The implementation of the `on_mouse` function-like macro. See the
+/// docs at the root of the crate for details.
+pub fn parse(input: TokenStream) -> TokenStream {
+  let mut handler = parse_macro_input!(input as Handler);
+  let crate_name = utils::crate_name();
+
+  for arm in &mut handler.arms {
+    if let Err(err) = expand_shorthands(&mut arm.pat) {
+      return err.to_compile_error().into();
+    }
+  }
+
+  handler
+    .expand(
+      quote! { #crate_name::event::MouseEvent },
+      quote! { #crate_name::event::{self, MouseButton, MouseEvent, MouseEventKind, KeyModifiers, handler::Propagate} },
+      &crate_name,
+    )
+    .into()
+}
@@ -1,43 +1,75 @@
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
-use quote::{quote, ToTokens};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{
   braced, parenthesized,
   parse::{Parse, ParseStream},
-  parse_macro_input,
+  parse_macro_input, parse_quote,
   punctuated::Punctuated,
+  spanned::Spanned,
   token::Brace,
-  Expr, Ident, Result, Token,
+  Error, Expr, Ident, Pat, PatOr, Result, Token,
 };
 
+use crate::utils;
+
 struct Component {
   name: Ident,
-  params: Punctuated<Param, Token![,]>,
-  children: Vec<Component>,
+  key: Option<Box<Expr>>,
+  params: Vec<Param>,
+  children: Vec<Child>,
 }
 
 impl ToTokens for Component {
   fn to_tokens(&self, tokens: &mut TokenStream2) {
-    let Self { name, params, children } = self;
-    let params = params.iter();
+    let Self {
+      name,
+      key,
+      params,
+      children,
+    } = self;
 
     let children = if children.is_empty() {
       quote! {}
+    } else if children.iter().any(Child::is_dynamic) {
+      // dynamic children have a length only known at runtime, so all children are
+      // collected into a `Vec` instead of an array
+      let children = collect(children, None);
+
+      quote! {
+        children: #children.into(),
+      }
     } else {
+      let children = children.iter().filter_map(Child::component);
+
       quote! {
         children: [#(#children,)*].into(),
       }
     };
 
-    tokens.extend(quote! {
-      #name {
-        #(#params,)*
+    let component = quote! {
+      {
+        #[allow(clippy::needless_update)]
+        let component = #name {
+          #(#params,)*
 
-        #children
+          #children
 
-        ..Default::default()
-      }.into()
-    });
+          ..Default::default()
+        };
+
+        component.into()
+      }
+    };
+
+    match key {
+      Some(key) => {
+        let site = site(name.span());
+        keyed(quote! { (#site, #key) }, component).to_tokens(tokens);
+      }
+
+      None => component.to_tokens(tokens),
+    }
   }
 }
 
@@ -46,38 +78,375 @@ impl Parse for Component {
     let name = input.parse()?;
     let params;
     parenthesized!(params in input);
-    let params = params.parse_terminated(Param::parse)?;
+    let params: Punctuated<Param, Token![,]> = params.parse_terminated(Param::parse)?;
 
-    let mut children = Vec::new();
+    // the `key` parameter is taken by the `Keyed` component wrapping this one
+    let mut key = None;
+    let mut rest = Vec::new();
+    for param in params {
+      match param {
+        Param::Field(ident) if ident == "key" => key = Some(Box::new(parse_quote! { #ident })),
+        Param::Pair(ident, expr) if ident == "key" => key = Some(expr),
+        Param::TryPair(ident, _) if ident == "key" => {
+          return Err(Error::new(ident.span(), "the `key` parameter cannot be converted with `try`"))
+        }
+        param => rest.push(param),
+      }
+    }
 
-    if input.lookahead1().peek(Brace) {
-      let content;
-      braced!(content in input);
-      while !content.is_empty() {
-        children.push(content.parse()?);
+    let children = if input.lookahead1().peek(Brace) {
+      parse_children(input)?
+    } else {
+      Vec::new()
+    };
+
+    Ok(Self {
+      name,
+      key,
+      params: rest,
+      children,
+    })
+  }
+}
+
+/// Parses children surrounded by braces.
+fn parse_children(input: ParseStream) -> Result<Vec<Child>> {
+  let content;
+  braced!(content in input);
+
+  let mut children = Vec::new();
+  while !content.is_empty() {
+    children.push(content.parse()?);
+  }
+
+  Ok(children)
+}
+
+/// Returns an expression identifying the location of `span` in the source.
+fn site(span: Span) -> TokenStream2 {
+  quote_spanned! {span=> (::std::file!(), ::std::line!(), ::std::column!()) }
+}
+
+/// Wraps `component` in a `Keyed` component with the key `key`.
+fn keyed(key: TokenStream2, component: TokenStream2) -> TokenStream2 {
+  let crate_name = utils::crate_name();
+
+  quote! {
+    {
+      #[allow(clippy::needless_update)]
+      let component = #crate_name::components::Keyed {
+        key: ::std::convert::Into::<#crate_name::components::Key>::into(#key),
+        children: [#component].into(),
+
+        ..Default::default()
+      };
+
+      component.into()
+    }
+  }
+}
+
+/// Expands `children` into an expression evaluating to a `Vec` of components.
+///
+/// Components that are not given a key are keyed by `prefix` and their position, if
+/// `prefix` is not `None`. The children of nested `if`, `match`, and `for` constructs are
+/// keyed by `prefix`, the location of the construct, and their branch or iteration.
+fn collect(children: &[Child], prefix: Option<&TokenStream2>) -> TokenStream2 {
+  let crate_name = utils::crate_name();
+  let scoped = |span: Span| {
+    let site = site(span);
+
+    match prefix {
+      Some(prefix) => quote! { (#prefix, #site) },
+      None => site,
+    }
+  };
+
+  let children = children.iter().enumerate().map(|(i, child)| match child {
+    Child::Component(component) => match (&component.key, prefix) {
+      (None, Some(prefix)) => {
+        let component = keyed(quote! { (#prefix, #i) }, component.to_token_stream());
+        quote! { __children.push(#component); }
+      }
+
+      _ => quote! { __children.push(#component); },
+    },
+
+    Child::Spread(expr) => quote! { __children.extend(#expr); },
+
+    Child::If(expr) => {
+      let expr = expr.expand(&scoped(expr.span), 0);
+      quote! { __children.extend(#expr); }
+    }
+
+    Child::Match(Match { span, expr, arms }) => {
+      let scope = scoped(*span);
+      let arms = arms.iter().enumerate().map(|(i, arm)| {
+        let Arm { pat, guard, children } = arm;
+        let guard = guard.as_ref().map(|guard| quote! { if #guard });
+        let children = collect(children, Some(&quote! { (#scope, #i) }));
+
+        quote! { #pat #guard => #children, }
+      });
+
+      quote! {
+        __children.extend(match #expr { #(#arms)* });
+      }
+    }
+
+    Child::For(For { span, pat, expr, children }) => {
+      let scope = scoped(*span);
+      let children = collect(children, Some(&quote! { __key }));
+
+      // the key is bound before the body, since the body of a nested loop shadows `__index`
+      quote! {
+        for (__index, #pat) in ::std::iter::IntoIterator::into_iter(#expr).enumerate() {
+          let __key = (#scope, __index);
+          __children.extend(#children);
+        }
       }
     }
+  });
+
+  quote! {
+    {
+      let mut __children: ::std::vec::Vec<#crate_name::components::Any> = ::std::vec::Vec::new();
+      #(#children)*
 
-    Ok(Self { name, params, children })
+      __children
+    }
+  }
+}
+
+/// A child of a component.
+enum Child {
+  /// A component.
+  Component(Component),
+  /// A collection of components spread into the children with `..`.
+  Spread(Box<Expr>),
+  /// An `if` expression, with children in each branch.
+  If(If),
+  /// A `match` expression, with children in each arm.
+  Match(Match),
+  /// A `for` loop, with children in its body.
+  For(For),
+}
+
+impl Child {
+  /// Returns whether the number of components this child expands into is only known
+  /// at runtime.
+  fn is_dynamic(&self) -> bool {
+    self.component().is_none()
+  }
+
+  fn component(&self) -> Option<&Component> {
+    match self {
+      Self::Component(component) => Some(component),
+      _ => None,
+    }
+  }
+}
+
+impl Parse for Child {
+  fn parse(input: ParseStream) -> Result<Self> {
+    if input.peek(Token![..]) {
+      input.parse::<Token![..]>()?;
+      Ok(Self::Spread(input.parse()?))
+    } else if input.peek(Token![if]) {
+      Ok(Self::If(input.parse()?))
+    } else if input.peek(Token![match]) {
+      Ok(Self::Match(input.parse()?))
+    } else if input.peek(Token![for]) {
+      Ok(Self::For(input.parse()?))
+    } else {
+      Ok(Self::Component(input.parse()?))
+    }
+  }
+}
+
+struct If {
+  span: Span,
+  cond: Box<Expr>,
+  then: Vec<Child>,
+  otherwise: Else,
+}
+
+enum Else {
+  None,
+  If(Box<If>),
+  Children(Vec<Child>),
+}
+
+impl If {
+  /// Expands into an `if` expression evaluating to a `Vec` of components, where the
+  /// components of the `n`th branch are keyed by `(scope, n)`.
+  fn expand(&self, scope: &TokenStream2, branch: usize) -> TokenStream2 {
+    let Self { cond, then, otherwise, .. } = self;
+    let then = collect(then, Some(&quote! { (#scope, #branch) }));
+
+    let otherwise = match otherwise {
+      Else::None => quote! { ::std::vec::Vec::new() },
+      Else::If(expr) => expr.expand(scope, branch + 1),
+      Else::Children(children) => {
+        let branch = branch + 1;
+        collect(children, Some(&quote! { (#scope, #branch) }))
+      }
+    };
+
+    quote! {
+      if #cond #then else { #otherwise }
+    }
+  }
+}
+
+impl Parse for If {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let span = input.parse::<Token![if]>()?.span;
+    let cond = Box::new(Expr::parse_without_eager_brace(input)?);
+    let then = parse_children(input)?;
+
+    let otherwise = if input.peek(Token![else]) {
+      input.parse::<Token![else]>()?;
+
+      if input.peek(Token![if]) {
+        Else::If(Box::new(input.parse()?))
+      } else {
+        Else::Children(parse_children(input)?)
+      }
+    } else {
+      Else::None
+    };
+
+    Ok(Self {
+      span,
+      cond,
+      then,
+      otherwise,
+    })
+  }
+}
+
+struct Match {
+  span: Span,
+  expr: Box<Expr>,
+  arms: Vec<Arm>,
+}
+
+struct Arm {
+  pat: Pat,
+  guard: Option<Box<Expr>>,
+  children: Vec<Child>,
+}
+
+impl Parse for Match {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let span = input.parse::<Token![match]>()?.span;
+    let expr = Box::new(Expr::parse_without_eager_brace(input)?);
+
+    let content;
+    braced!(content in input);
+
+    let mut arms = Vec::new();
+    while !content.is_empty() {
+      arms.push(content.parse()?);
+    }
+
+    Ok(Self { span, expr, arms })
+  }
+}
+
+impl Parse for Arm {
+  fn parse(input: ParseStream) -> Result<Self> {
+    // `syn` does not expose parsing of top-level or-patterns, so they are parsed here
+    let leading_vert: Option<Token![|]> = input.parse()?;
+    let mut cases = Punctuated::new();
+    cases.push_value(input.parse()?);
+    while input.peek(Token![|]) && !input.peek(Token![||]) {
+      cases.push_punct(input.parse()?);
+      cases.push_value(input.parse()?);
+    }
+
+    let pat = if leading_vert.is_none() && cases.len() == 1 {
+      cases.into_iter().next().expect("missing pattern")
+    } else {
+      Pat::Or(PatOr {
+        attrs: Vec::new(),
+        leading_vert,
+        cases,
+      })
+    };
+
+    let guard = if input.peek(Token![if]) {
+      input.parse::<Token![if]>()?;
+      Some(Box::new(input.parse()?))
+    } else {
+      None
+    };
+
+    input.parse::<Token![=>]>()?;
+
+    let children = if input.peek(Brace) {
+      parse_children(input)?
+    } else {
+      vec![input.parse()?]
+    };
+
+    if input.peek(Token![,]) {
+      input.parse::<Token![,]>()?;
+    }
+
+    Ok(Self { pat, guard, children })
+  }
+}
+
+struct For {
+  span: Span,
+  pat: Pat,
+  expr: Box<Expr>,
+  children: Vec<Child>,
+}
+
+impl Parse for For {
+  fn parse(input: ParseStream) -> Result<Self> {
+    let span = input.parse::<Token![for]>()?.span;
+    let pat = input.parse()?;
+    input.parse::<Token![in]>()?;
+    let expr = Box::new(Expr::parse_without_eager_brace(input)?);
+    let children = parse_children(input)?;
+
+    Ok(Self { span, pat, expr, children })
   }
 }
 
 enum Param {
   Field(Ident),
   Pair(Ident, Box<Expr>),
+  /// A parameter converted with `TryInto`, written as `ident: try expr`.
+  TryPair(Ident, Box<Expr>),
 }
 
 impl ToTokens for Param {
   fn to_tokens(&self, tokens: &mut TokenStream2) {
-    let (ident, value) = match self {
-      Self::Field(ident) => (ident, quote! { #ident }),
-      Self::Pair(ident, expr) => (ident, quote! { #expr }),
-    };
+    let crate_name = utils::crate_name();
+
+    // the conversions are spanned to the value, so that type errors point at the argument
+    // instead of the whole macro invocation
+    tokens.extend(match self {
+      Self::Field(ident) => quote_spanned! {ident.span()=>
+        #ident: #crate_name::components::param::FromParam::from_param(#ident)
+      },
 
-    tokens.extend(quote! {
-      #ident: #value
-        .try_into()
-        .expect(&format!("try into failed for argument: '{}'", stringify!(#ident)))
+      Self::Pair(ident, expr) => {
+        let value = utils::from_param(expr, &crate_name);
+        quote! { #ident: #value }
+      }
+
+      Self::TryPair(ident, expr) => quote_spanned! {expr.span()=>
+        #ident: ::std::result::Result::expect(
+          ::std::convert::TryInto::try_into(#expr),
+          ::std::concat!("try into failed for parameter `", ::std::stringify!(#ident), "`"),
+        )
+      },
     });
   }
 }
@@ -87,7 +456,13 @@ impl Parse for Param {
     let ident: Ident = input.parse()?;
     if input.lookahead1().peek(Token![:]) {
       input.parse::<Token![:]>()?;
-      Ok(Self::Pair(ident, input.parse()?))
+
+      if input.peek(Token![try]) {
+        input.parse::<Token![try]>()?;
+        Ok(Self::TryPair(ident, input.parse()?))
+      } else {
+        Ok(Self::Pair(ident, input.parse()?))
+      }
     } else {
       Ok(Self::Field(ident))
     }
@@ -1,7 +1,7 @@
 use proc_macro2::{Span, TokenStream};
 use proc_macro_crate::FoundCrate;
-use quote::quote;
-use syn::Ident;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Expr, ExprLit, ExprUnary, Ident, Lit, UnOp};
 
 pub fn crate_name() -> TokenStream {
   let crate_name = proc_macro_crate::crate_name("intuitive").unwrap();
@@ -15,3 +15,28 @@ pub fn crate_name() -> TokenStream {
     }
   }
 }
+
+/// Returns an expression converting `expr` into a parameter of a component.
+///
+/// The conversion is spanned to `expr`, so that type errors point at it instead of the
+/// whole macro invocation. Unsuffixed numeric literals would default to `i32`, so their
+/// type is inferred from the parameter instead.
+pub fn from_param(expr: &Expr, crate_name: &TokenStream) -> TokenStream {
+  if is_unsuffixed_number(expr) {
+    quote_spanned! {expr.span()=> #crate_name::components::param::from_literal(#expr) }
+  } else {
+    quote_spanned! {expr.span()=> #crate_name::components::param::FromParam::from_param(#expr) }
+  }
+}
+
+/// Returns whether `expr` is a numeric literal without a type suffix, such as `5` or `-1.5`.
+fn is_unsuffixed_number(expr: &Expr) -> bool {
+  match expr {
+    Expr::Lit(ExprLit { lit: Lit::Int(lit), .. }) => lit.suffix().is_empty(),
+    Expr::Lit(ExprLit { lit: Lit::Float(lit), .. }) => lit.suffix().is_empty(),
+    Expr::Unary(ExprUnary {
+      op: UnOp::Neg(_), expr, ..
+    }) => is_unsuffixed_number(expr),
+    _ => false,
+  }
+}